@@ -0,0 +1,89 @@
+use ase::ring_buffer::RingBuffer;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+
+const BLOCK_SIZES: [usize; 3] = [64, 256, 1024];
+
+fn bench_push_pop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ring_buffer/push_pop");
+    for &block_size in &BLOCK_SIZES {
+        group.throughput(Throughput::Elements(block_size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(block_size), &block_size, |b, &n| {
+            let mut buffer = RingBuffer::<f32>::new(n);
+            b.iter(|| {
+                for i in 0..n {
+                    buffer.push(black_box(i as f32));
+                }
+                for _ in 0..n {
+                    black_box(buffer.pop());
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_bulk_copy(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ring_buffer/write_from_read_into");
+    for &block_size in &BLOCK_SIZES {
+        group.throughput(Throughput::Elements(block_size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(block_size), &block_size, |b, &n| {
+            // Odd capacity so the copies keep wrapping at different points.
+            let mut buffer = RingBuffer::<f32>::new(n + 7);
+            let input = vec![0.5f32; n];
+            let mut output = vec![0.0f32; n];
+            b.iter(|| {
+                buffer.write_from(black_box(&input));
+                black_box(buffer.read_into(&mut output));
+            });
+        });
+    }
+    group.finish();
+}
+
+fn bench_delay_line(c: &mut Criterion) {
+    bench_delay_line_with(c, "ring_buffer/delay_line", RingBuffer::new);
+    bench_delay_line_with(c, "ring_buffer/delay_line_power_of_two", RingBuffer::new_power_of_two);
+}
+
+fn bench_delay_line_with(c: &mut Criterion, name: &str, make: fn(usize) -> RingBuffer<f32>) {
+    let mut group = c.benchmark_group(name);
+    for &block_size in &BLOCK_SIZES {
+        group.throughput(Throughput::Elements(block_size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(block_size), &block_size, |b, &n| {
+            let mut buffer = make(4410);
+            let input = vec![0.5f32; n];
+            let mut output = vec![0.0f32; n];
+            b.iter(|| {
+                for (x, y) in input.iter().zip(output.iter_mut()) {
+                    buffer.push(*x);
+                    *y = buffer.get(black_box(100));
+                }
+                black_box(&output);
+            });
+        });
+    }
+    group.finish();
+}
+
+// Compare with and without `--features simd`.
+fn bench_multi_tap(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ring_buffer/tap_frac_multi");
+    for &block_size in &BLOCK_SIZES {
+        group.throughput(Throughput::Elements(block_size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(block_size), &block_size, |b, &n| {
+            let mut buffer = RingBuffer::<f32>::new(4410);
+            buffer.write_from(&vec![0.5f32; 4410]);
+            let delays: Vec<f32> = (0..n).map(|i| 100.0 + i as f32 * 2.7).collect();
+            let mut output = vec![0.0f32; n];
+            b.iter(|| {
+                buffer.tap_frac_multi(black_box(&delays), &mut output);
+                black_box(&output);
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_push_pop, bench_bulk_copy, bench_delay_line, bench_multi_tap);
+criterion_main!(benches);
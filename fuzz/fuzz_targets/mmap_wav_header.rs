@@ -0,0 +1,13 @@
+#![no_main]
+
+use ase::mmap_wav;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok((spec, start, end)) = mmap_wav::parse_header(data) {
+        assert!(start <= end && end <= data.len());
+        assert_eq!(spec.bits_per_sample, 16);
+        assert!(spec.channels > 0);
+        assert!(spec.sample_rate > 0);
+    }
+});
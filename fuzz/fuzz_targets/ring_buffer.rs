@@ -0,0 +1,93 @@
+#![no_main]
+
+use std::sync::OnceLock;
+
+use ase::delay_line::{DelayLine, Interpolation};
+use ase::frame_ring_buffer::FrameRingBuffer;
+use ase::ring_buffer::{AllpassState, RingBuffer, SincTable};
+use libfuzzer_sys::fuzz_target;
+
+fn sinc_table() -> &'static SincTable {
+    static TABLE: OnceLock<SincTable> = OnceLock::new();
+    TABLE.get_or_init(|| SincTable::new(8))
+}
+
+// Keeps delay-line input bounded so its output must stay finite.
+fn bounded(value: f32) -> f32 {
+    if value.is_finite() {
+        value % 1.0
+    } else {
+        0.0
+    }
+}
+
+// The first byte picks the capacity; every following 5-byte chunk is one
+// operation with a 32-bit argument, used as an index or as raw f32 bits so
+// NaN, infinities and huge offsets all reach the fractional reads.
+fuzz_target!(|data: &[u8]| {
+    let Some((&capacity, ops)) = data.split_first() else {
+        return;
+    };
+    let capacity = capacity as usize % 64 + 1;
+    let mut buffer = RingBuffer::<f32>::new(capacity);
+    let mut allpass = AllpassState::new();
+    let mut frames = FrameRingBuffer::new(2, capacity);
+    let mut delay = DelayLine::new(0.01, 1000.0);
+    for op in ops.chunks_exact(5) {
+        let bits = u32::from_le_bytes([op[1], op[2], op[3], op[4]]);
+        let index = bits as usize;
+        let value = f32::from_bits(bits);
+        match op[0] % 16 {
+            0 => buffer.push(value),
+            1 => {
+                buffer.pop();
+            }
+            2 => buffer.set_read_index(index),
+            3 => buffer.set_write_index(index),
+            4 => {
+                buffer.get(index);
+            }
+            5 => {
+                buffer.tap(index);
+            }
+            6 => {
+                buffer.get_frac(value);
+            }
+            7 => {
+                buffer.get_frac_cubic(value);
+            }
+            8 => {
+                buffer.get_frac_allpass(value, &mut allpass);
+            }
+            9 => {
+                buffer.get_frac_sinc(value, sinc_table());
+            }
+            10 => {
+                let delays = [value, -value, value * 0.5, value + 1.0, 0.0, value, f32::NAN, value - 1.0, value];
+                let mut output = [0.0; 9];
+                buffer.tap_frac_multi(&delays, &mut output);
+            }
+            11 => frames.push_frame(&[value, -value]),
+            12 => {
+                let mut output = [0.0; 2];
+                frames.tap_frac(value, &mut output);
+            }
+            13 => delay.set_delay_samples(value),
+            14 => match op[1] % 5 {
+                0 => delay.set_interpolation(Interpolation::Linear),
+                1 => delay.set_interpolation(Interpolation::Cubic),
+                2 => delay.set_interpolation(Interpolation::Allpass),
+                3 => delay.set_feedback(value),
+                _ => delay.set_mix(value),
+            },
+            _ => {
+                let output = delay.process_sample(bounded(value));
+                assert!(output.is_finite(), "delay line output {}", output);
+            }
+        }
+        assert!(buffer.len() <= capacity);
+        assert!(buffer.get_read_index() < capacity);
+        assert!(buffer.get_write_index() < capacity);
+        assert_eq!((buffer.get_read_index() + buffer.len()) % capacity, buffer.get_write_index());
+    }
+});
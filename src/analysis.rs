@@ -0,0 +1,203 @@
+use crate::units::{ratio_to_cents, seconds_to_samples};
+
+const MIN_PITCH_HZ: f32 = 50.0;
+const MAX_PITCH_HZ: f32 = 2000.0;
+const FRAME_SECONDS: f32 = 0.04;
+const HOP_SECONDS: f32 = 0.005;
+const VOICING_THRESHOLD: f32 = 0.2;
+// Below this depth the contour is pitch-tracking jitter, not vibrato, and
+// its "rate" means nothing.
+const MIN_DEPTH_CENTS: f32 = 5.0;
+const MIN_CONTOUR_FRAMES: usize = 8;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ModulationEstimate {
+    pub center_hz: f32,
+    pub rate_hz: f32,
+    pub depth_cents: f32,
+}
+
+/// Estimates vibrato rate and depth of a (mono) signal by tracking its pitch
+/// frame by frame and measuring the periodicity and swing of the contour.
+/// Returns None when the signal is too short or not pitched enough. The rate
+/// is 0 when there is no measurable modulation.
+pub fn estimate_modulation(samples: &[f32], sample_rate: f32) -> Option<ModulationEstimate> {
+    let frame_len = seconds_to_samples(FRAME_SECONDS, sample_rate) as usize;
+    let hop = (seconds_to_samples(HOP_SECONDS, sample_rate) as usize).max(1);
+    let min_lag = ((sample_rate / MAX_PITCH_HZ) as usize).max(2);
+    let max_lag = ((sample_rate / MIN_PITCH_HZ) as usize).min(frame_len / 2);
+    if min_lag >= max_lag || samples.len() < frame_len {
+        return None;
+    }
+
+    // Pitch per voiced frame, tagged with its hop index so gaps left by
+    // unvoiced frames are not mistaken for contiguous time.
+    let mut contour = Vec::new();
+    let mut hop_indices = Vec::new();
+    let mut start = 0;
+    while start + frame_len <= samples.len() {
+        let frame = &samples[start..start + frame_len];
+        if let Some(period) = detect_period(frame, min_lag, max_lag) {
+            contour.push(sample_rate / period);
+            hop_indices.push(start / hop);
+        }
+        start += hop;
+    }
+    if contour.len() < MIN_CONTOUR_FRAMES {
+        return None;
+    }
+
+    let mean_log = contour.iter().map(|f| f.log2()).sum::<f32>() / contour.len() as f32;
+    let center_hz = mean_log.exp2();
+    let cents: Vec<f32> = contour.iter().map(|f| ratio_to_cents(f / center_hz)).collect();
+
+    // Peak deviation of a sinusoidal contour is sqrt(2) times its RMS.
+    let rms = (cents.iter().map(|c| c * c).sum::<f32>() / cents.len() as f32).sqrt();
+    let depth_cents = rms * std::f32::consts::SQRT_2;
+
+    // The rate comes from the longest gap-free stretch of the contour.
+    let frame_rate = sample_rate / hop as f32;
+    let run = longest_run(&hop_indices);
+    let rate_hz = if depth_cents < MIN_DEPTH_CENTS || run.len() < MIN_CONTOUR_FRAMES {
+        0.0
+    } else {
+        contour_period(&cents[run]).map_or(0.0, |period| frame_rate / period)
+    };
+
+    Some(ModulationEstimate {
+        center_hz,
+        rate_hz,
+        depth_cents,
+    })
+}
+
+// YIN-style cumulative mean normalized difference, refined with parabolic
+// interpolation so the period is fractional.
+fn detect_period(frame: &[f32], min_lag: usize, max_lag: usize) -> Option<f32> {
+    let window = frame.len() - max_lag;
+    let mut diff = vec![0.0f32; max_lag + 1];
+    for (lag, d) in diff.iter_mut().enumerate().skip(1) {
+        *d = (0..window).map(|i| (frame[i] - frame[i + lag]).powi(2)).sum();
+    }
+
+    let mut running_sum = 0.0;
+    let mut normalized = vec![1.0f32; max_lag + 1];
+    for lag in 1..=max_lag {
+        running_sum += diff[lag];
+        if running_sum > 0.0 {
+            normalized[lag] = diff[lag] * lag as f32 / running_sum;
+        }
+    }
+
+    let mut lag = min_lag;
+    while lag < max_lag {
+        if normalized[lag] < VOICING_THRESHOLD {
+            while lag + 1 < max_lag && normalized[lag + 1] < normalized[lag] {
+                lag += 1;
+            }
+            return Some(lag as f32 + parabolic_offset(&normalized, lag, false));
+        }
+        lag += 1;
+    }
+    None
+}
+
+// Range of the longest stretch of consecutive hop indices.
+fn longest_run(hop_indices: &[usize]) -> core::ops::Range<usize> {
+    let mut best = 0..0;
+    let mut run_start = 0;
+    for i in 1..=hop_indices.len() {
+        if i == hop_indices.len() || hop_indices[i] != hop_indices[i - 1] + 1 {
+            if i - run_start > best.len() {
+                best = run_start..i;
+            }
+            run_start = i;
+        }
+    }
+    best
+}
+
+// Lag of the first autocorrelation peak after the first zero crossing.
+fn contour_period(contour: &[f32]) -> Option<f32> {
+    let max_lag = contour.len() / 2;
+    let autocorr: Vec<f32> = (0..=max_lag)
+        .map(|lag| {
+            contour[..contour.len() - lag]
+                .iter()
+                .zip(&contour[lag..])
+                .map(|(a, b)| a * b)
+                .sum()
+        })
+        .collect();
+
+    let first_negative = autocorr.iter().position(|&r| r < 0.0)?;
+    let peak = (first_negative..max_lag).max_by(|&a, &b| autocorr[a].total_cmp(&autocorr[b]))?;
+    if autocorr[peak] <= 0.0 {
+        return None;
+    }
+    Some(peak as f32 + parabolic_offset(&autocorr, peak, true))
+}
+
+fn parabolic_offset(values: &[f32], index: usize, maximum: bool) -> f32 {
+    if index == 0 || index + 1 >= values.len() {
+        return 0.0;
+    }
+    let (a, b, c) = (values[index - 1], values[index], values[index + 1]);
+    let denominator = a - 2.0 * b + c;
+    if denominator.abs() <= f32::EPSILON || (maximum && denominator > 0.0) {
+        return 0.0;
+    }
+    (0.5 * (a - c) / denominator).clamp(-0.5, 0.5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::estimate_modulation;
+    use crate::units::cents_to_ratio;
+    use std::f32::consts::TAU;
+
+    fn vibrato_tone(sample_rate: f32, seconds: f32, freq: f32, rate: f32, depth_cents: f32) -> Vec<f32> {
+        let mut phase = 0.0f32;
+        (0..(seconds * sample_rate) as usize)
+            .map(|n| {
+                let t = n as f32 / sample_rate;
+                let cents = depth_cents * (TAU * rate * t).sin();
+                phase += TAU * freq * cents_to_ratio(cents) / sample_rate;
+                phase.sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_detects_rate_and_depth() {
+        let samples = vibrato_tone(8000.0, 2.0, 220.0, 5.0, 50.0);
+        let estimate = estimate_modulation(&samples, 8000.0).unwrap();
+        assert!((estimate.center_hz - 220.0).abs() < 2.0);
+        assert!((estimate.rate_hz - 5.0).abs() < 0.25);
+        assert!((estimate.depth_cents - 50.0).abs() < 5.0);
+    }
+
+    #[test]
+    fn test_steady_tone_has_no_depth() {
+        let samples = vibrato_tone(8000.0, 1.0, 330.0, 5.0, 0.0);
+        let estimate = estimate_modulation(&samples, 8000.0).unwrap();
+        assert!(estimate.depth_cents < 1.0);
+        assert_eq!(estimate.rate_hz, 0.0);
+    }
+
+    #[test]
+    fn test_rate_ignores_unvoiced_gaps() {
+        // Two vibrato notes separated by silence.
+        let mut samples = vibrato_tone(8000.0, 0.5, 220.0, 5.0, 50.0);
+        samples.extend([0.0; 2400]);
+        samples.extend(vibrato_tone(8000.0, 0.5, 220.0, 5.0, 50.0));
+        let estimate = estimate_modulation(&samples, 8000.0).unwrap();
+        assert!((estimate.rate_hz - 5.0).abs() < 0.25, "rate {}", estimate.rate_hz);
+    }
+
+    #[test]
+    fn test_silence_and_short_input() {
+        assert!(estimate_modulation(&[0.0; 16000], 8000.0).is_none());
+        assert!(estimate_modulation(&[0.5; 10], 8000.0).is_none());
+    }
+}
@@ -0,0 +1,108 @@
+use std::f32::consts::FRAC_PI_2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FadeCurve {
+    /// Gains sum to 1: right for correlated material such as overlapping
+    /// chunks of the same render.
+    Linear,
+    /// Powers sum to 1: right for unrelated takes.
+    EqualPower,
+}
+
+/// (outgoing, incoming) gains at `position` in 0..=1 through the fade.
+pub fn fade_gains(curve: FadeCurve, position: f32) -> (f32, f32) {
+    let position = position.clamp(0.0, 1.0);
+    match curve {
+        FadeCurve::Linear => (1.0 - position, position),
+        FadeCurve::EqualPower => ((position * FRAC_PI_2).cos(), (position * FRAC_PI_2).sin()),
+    }
+}
+
+/// Mixes two equally long interleaved regions into `output`, fading from
+/// `outgoing` to `incoming`. Does nothing when `channels` is 0.
+pub fn crossfade(outgoing: &[f32], incoming: &[f32], output: &mut [f32], channels: usize, curve: FadeCurve) {
+    if channels == 0 {
+        return;
+    }
+    let frames = output.len().min(outgoing.len()).min(incoming.len()) / channels;
+    for frame in 0..frames {
+        let position = (frame as f32 + 0.5) / frames as f32;
+        let (out_gain, in_gain) = fade_gains(curve, position);
+        for channel in 0..channels {
+            let i = frame * channels + channel;
+            output[i] = outgoing[i] * out_gain + incoming[i] * in_gain;
+        }
+    }
+}
+
+/// Joins two interleaved signals, blending the last `overlap_frames` of
+/// `first` with the first `overlap_frames` of `second`. With 0 channels the
+/// signals are simply concatenated.
+pub fn splice(first: &[f32], second: &[f32], overlap_frames: usize, channels: usize, curve: FadeCurve) -> Vec<f32> {
+    let overlap = if channels == 0 {
+        0
+    } else {
+        let overlap = overlap_frames.saturating_mul(channels).min(first.len()).min(second.len());
+        overlap - overlap % channels
+    };
+    let split = first.len() - overlap;
+
+    let mut output = Vec::with_capacity(first.len() + second.len() - overlap);
+    output.extend_from_slice(&first[..split]);
+    output.resize(split + overlap, 0.0);
+    crossfade(&first[split..], &second[..overlap], &mut output[split..], channels, curve);
+    output.extend_from_slice(&second[overlap..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crossfade, fade_gains, splice, FadeCurve};
+
+    #[test]
+    fn test_fade_gains() {
+        for i in 0..=10 {
+            let position = i as f32 / 10.0;
+            let (a, b) = fade_gains(FadeCurve::Linear, position);
+            assert!((a + b - 1.0).abs() < 1e-6);
+            let (a, b) = fade_gains(FadeCurve::EqualPower, position);
+            assert!((a * a + b * b - 1.0).abs() < 1e-6);
+        }
+        assert_eq!(fade_gains(FadeCurve::Linear, 0.0), (1.0, 0.0));
+    }
+
+    #[test]
+    fn test_linear_splice_of_identical_signal_is_seamless() {
+        let signal: Vec<f32> = (0..20).map(|i| i as f32).collect();
+        let output = splice(&signal[..12], &signal[8..], 4, 1, FadeCurve::Linear);
+        assert_eq!(output.len(), 20);
+        for (a, b) in output.iter().zip(&signal) {
+            assert!((a - b).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_stereo_splice_and_short_inputs() {
+        let first = [1.0, -1.0, 1.0, -1.0, 1.0, -1.0];
+        let second = [0.0; 6];
+        let output = splice(&first, &second, 2, 2, FadeCurve::Linear);
+        assert_eq!(output.len(), 8);
+        assert_eq!(&output[..2], &[1.0, -1.0]);
+        assert!(output[2] > 0.0 && output[3] < 0.0);
+        assert_eq!(&output[6..], &[0.0, 0.0]);
+
+        let output = splice(&[1.0], &[2.0, 3.0], 10, 1, FadeCurve::EqualPower);
+        assert_eq!(output.len(), 2);
+        assert_eq!(output[1], 3.0);
+    }
+
+    #[test]
+    fn test_extreme_overlap_and_zero_channels() {
+        let output = splice(&[1.0, 1.0], &[0.0, 0.0], usize::MAX, 2, FadeCurve::Linear);
+        assert_eq!(output.len(), 2);
+        assert_eq!(splice(&[1.0], &[2.0], 4, 0, FadeCurve::Linear), [1.0, 2.0]);
+        let mut output = [5.0; 2];
+        crossfade(&[1.0; 2], &[0.0; 2], &mut output, 0, FadeCurve::Linear);
+        assert_eq!(output, [5.0; 2]);
+    }
+}
@@ -0,0 +1,241 @@
+use crate::ring_buffer::{AllpassState, RingBuffer, ALLPASS_MIN_OFFSET};
+use crate::units::seconds_to_samples;
+
+// Extra history so cubic reads at the maximum delay stay inside the buffer.
+const INTERPOLATION_MARGIN: usize = 3;
+const MAX_FEEDBACK: f32 = 0.999;
+/// Longest delay a `DelayLine` allocates for, about six minutes at 44.1 kHz.
+pub const MAX_DELAY_SAMPLES: usize = 1 << 24;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    Linear,
+    /// 4-point Catmull-Rom.
+    Cubic,
+    /// First-order allpass; flat magnitude, best for slowly moving delays.
+    Allpass,
+}
+
+/// Fractional delay with feedback and dry/wet mix, the shared core of
+/// vibrato, chorus, flanger and echo style effects. Setters ignore NaN and
+/// infinite values so one bad automation value cannot poison the line.
+pub struct DelayLine {
+    buffer: RingBuffer<f32>,
+    sample_rate: f32,
+    max_delay_samples: f32,
+    delay_samples: f32,
+    interpolation: Interpolation,
+    allpass: AllpassState,
+    feedback: f32,
+    mix: f32,
+}
+
+impl DelayLine {
+    /// Starts fully wet with a one-sample delay, linear interpolation and no
+    /// feedback. The maximum delay is capped at `MAX_DELAY_SAMPLES`; a
+    /// non-finite one falls back to the 2-sample minimum.
+    pub fn new(max_delay_seconds: f32, sample_rate: f32) -> Self {
+        let max_delay = seconds_to_samples(max_delay_seconds, sample_rate);
+        let max_delay_samples = if max_delay.is_finite() {
+            (max_delay.clamp(0.0, MAX_DELAY_SAMPLES as f32) as usize).max(2)
+        } else {
+            2
+        };
+        DelayLine {
+            buffer: RingBuffer::new_power_of_two(max_delay_samples + INTERPOLATION_MARGIN),
+            sample_rate,
+            max_delay_samples: max_delay_samples as f32,
+            delay_samples: 1.0,
+            interpolation: Interpolation::Linear,
+            allpass: AllpassState::new(),
+            feedback: 0.0,
+            mix: 1.0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.buffer.reset();
+        self.allpass.reset();
+    }
+
+    /// Clamped to between `min_delay_samples()` and the maximum delay.
+    pub fn set_delay_samples(&mut self, delay: f32) {
+        if !delay.is_finite() {
+            return;
+        }
+        self.delay_samples = delay.clamp(self.min_delay_samples(), self.max_delay_samples);
+    }
+
+    /// One sample, plus `ALLPASS_MIN_OFFSET` in allpass mode, whose filter
+    /// turns unstable at shorter fractional delays.
+    pub fn min_delay_samples(&self) -> f32 {
+        match self.interpolation {
+            Interpolation::Allpass => 1.0 + ALLPASS_MIN_OFFSET,
+            _ => 1.0,
+        }
+    }
+
+    pub fn set_delay_seconds(&mut self, seconds: f32) {
+        self.set_delay_samples(seconds_to_samples(seconds, self.sample_rate));
+    }
+
+    pub fn delay_samples(&self) -> f32 {
+        self.delay_samples
+    }
+
+    pub fn set_interpolation(&mut self, interpolation: Interpolation) {
+        if interpolation != self.interpolation {
+            self.interpolation = interpolation;
+            self.allpass.reset();
+            self.set_delay_samples(self.delay_samples);
+        }
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Amount of the delayed signal fed back into the line, kept just inside
+    /// (-1, 1) so the loop stays stable.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        if !feedback.is_finite() {
+            return;
+        }
+        self.feedback = feedback.clamp(-MAX_FEEDBACK, MAX_FEEDBACK);
+    }
+
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// 0.0 is the dry input only, 1.0 the delayed signal only.
+    pub fn set_mix(&mut self, mix: f32) {
+        if !mix.is_finite() {
+            return;
+        }
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+
+    pub fn mix(&self) -> f32 {
+        self.mix
+    }
+
+    pub fn process_sample(&mut self, input: f32) -> f32 {
+        // The current input is not pushed yet, so the newest value is already
+        // one sample old.
+        let offset = self.delay_samples - 1.0;
+        let delayed = match self.interpolation {
+            Interpolation::Linear => self.buffer.tap_frac(offset),
+            Interpolation::Cubic => self.buffer.get_frac_cubic(offset),
+            Interpolation::Allpass => self.buffer.get_frac_allpass(offset, &mut self.allpass),
+        };
+        self.buffer.push(input + self.feedback * delayed);
+        input + self.mix * (delayed - input)
+    }
+
+    pub fn process(&mut self, block: &mut [f32]) {
+        for sample in block.iter_mut() {
+            *sample = self.process_sample(*sample);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DelayLine, Interpolation, MAX_DELAY_SAMPLES};
+
+    fn impulse_response(delay: &mut DelayLine, len: usize) -> Vec<f32> {
+        let mut block = vec![0.0; len];
+        block[0] = 1.0;
+        delay.process(&mut block);
+        block
+    }
+
+    #[test]
+    fn test_integer_delay() {
+        let mut delay = DelayLine::new(0.01, 1000.0);
+        for interpolation in [Interpolation::Linear, Interpolation::Cubic, Interpolation::Allpass] {
+            delay.set_interpolation(interpolation);
+            for samples in [1, 4] {
+                delay.reset();
+                delay.set_delay_seconds(samples as f32 / 1000.0);
+                let response = impulse_response(&mut delay, 8);
+                if interpolation == Interpolation::Allpass && samples == 1 {
+                    // Raised to the allpass minimum, so the impulse is smeared.
+                    assert_eq!(delay.delay_samples(), 1.1);
+                    continue;
+                }
+                assert_eq!(delay.delay_samples(), samples as f32);
+                for (n, &sample) in response.iter().enumerate() {
+                    let expected = if n == samples { 1.0 } else { 0.0 };
+                    assert!((sample - expected).abs() < 1e-6, "{:?} at {}", interpolation, n);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_delay_change_while_running_decays() {
+        for interpolation in [Interpolation::Linear, Interpolation::Cubic, Interpolation::Allpass] {
+            let mut delay = DelayLine::new(0.01, 1000.0);
+            delay.set_interpolation(interpolation);
+            delay.set_delay_samples(3.5);
+            let mut block: Vec<f32> = (0..20).map(|n| (n as f32 * 0.7).sin()).collect();
+            delay.process(&mut block);
+            delay.set_delay_samples(1.0);
+            let mut tail = vec![0.0; 300];
+            delay.process(&mut tail);
+            assert!(tail[250..].iter().all(|s| s.abs() < 1e-6), "{:?} keeps ringing", interpolation);
+        }
+    }
+
+    #[test]
+    fn test_feedback_and_mix() {
+        let mut delay = DelayLine::new(0.01, 1000.0);
+        delay.set_delay_samples(3.0);
+        delay.set_feedback(0.5);
+        delay.set_mix(0.5);
+        let response = impulse_response(&mut delay, 10);
+        assert_eq!(response[0], 0.5);
+        assert_eq!(response[3], 0.5);
+        assert_eq!(response[6], 0.25);
+        assert_eq!(response[9], 0.125);
+        assert_eq!(response[1], 0.0);
+    }
+
+    #[test]
+    fn test_parameter_limits() {
+        let mut delay = DelayLine::new(0.01, 1000.0);
+        delay.set_delay_samples(100.0);
+        assert_eq!(delay.delay_samples(), 10.0);
+        delay.set_delay_seconds(0.0);
+        assert_eq!(delay.delay_samples(), 1.0);
+        delay.set_feedback(2.0);
+        assert!(delay.feedback() < 1.0);
+        delay.set_mix(-1.0);
+        assert_eq!(delay.mix(), 0.0);
+
+        delay.set_delay_samples(f32::NAN);
+        delay.set_feedback(f32::NAN);
+        delay.set_mix(f32::INFINITY);
+        assert_eq!(delay.delay_samples(), 1.0);
+        assert!(delay.feedback() < 1.0);
+        assert_eq!(delay.mix(), 0.0);
+        let mut block = [1.0; 8];
+        delay.process(&mut block);
+        assert!(block.iter().all(|s| s.is_finite()));
+    }
+
+    #[test]
+    fn test_extreme_max_delay() {
+        for max_seconds in [f32::INFINITY, f32::NAN, 0.0, -1.0] {
+            let mut delay = DelayLine::new(max_seconds, 48000.0);
+            delay.set_interpolation(Interpolation::Allpass);
+            delay.set_delay_samples(1e9);
+            assert!(delay.delay_samples() <= MAX_DELAY_SAMPLES as f32);
+            assert!(delay.process_sample(1.0).is_finite());
+        }
+        let delay = DelayLine::new(1e30, 48000.0);
+        assert_eq!(delay.delay_samples(), 1.0);
+    }
+}
@@ -0,0 +1,148 @@
+use alloc::{vec, vec::Vec};
+
+/// Circular buffer of interleaved multichannel frames. All channels of a
+/// frame sit next to each other, so a stereo delay touches one cache line
+/// per tap instead of one per channel buffer. Like `RingBuffer`, pushing
+/// into a full buffer overwrites the oldest frame.
+pub struct FrameRingBuffer {
+    samples: Vec<f32>,
+    channels: usize,
+    capacity: usize,
+    read_frame: usize,
+    write_frame: usize,
+    size: usize,
+}
+
+impl FrameRingBuffer {
+    /// `channels` is clamped to at least one.
+    pub fn new(channels: usize, frames: usize) -> Self {
+        let channels = channels.max(1);
+        FrameRingBuffer {
+            samples: vec![0.0; channels * frames],
+            channels,
+            capacity: frames,
+            read_frame: 0,
+            write_frame: 0,
+            size: 0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.fill(0.0);
+        self.read_frame = 0;
+        self.write_frame = 0;
+        self.size = 0;
+    }
+
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Capacity in frames.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of buffered frames.
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// `frame` must hold exactly `channels()` samples.
+    pub fn push_frame(&mut self, frame: &[f32]) {
+        assert_eq!(frame.len(), self.channels, "frame has the wrong channel count");
+        let start = self.write_frame * self.channels;
+        self.samples[start..start + self.channels].copy_from_slice(frame);
+
+        if self.size == self.capacity {
+            self.read_frame = (self.read_frame + 1) % self.capacity;
+        } else {
+            self.size += 1;
+        }
+        self.write_frame = (self.write_frame + 1) % self.capacity;
+    }
+
+    /// Pushes every whole frame of an interleaved block.
+    pub fn push_interleaved(&mut self, samples: &[f32]) {
+        for frame in samples.chunks_exact(self.channels) {
+            self.push_frame(frame);
+        }
+    }
+
+    /// Copies the oldest frame into `frame` and removes it. Returns false
+    /// and leaves `frame` untouched when empty.
+    pub fn pop_frame(&mut self, frame: &mut [f32]) -> bool {
+        if self.size == 0 {
+            return false;
+        }
+        frame.copy_from_slice(self.frame(self.read_frame));
+        self.read_frame = (self.read_frame + 1) % self.capacity;
+        self.size -= 1;
+        true
+    }
+
+    /// Frame pushed `delay_frames` pushes before the newest one; 0 is the
+    /// newest. Delays wrap around the capacity.
+    pub fn tap(&self, delay_frames: usize) -> &[f32] {
+        self.frame(self.index_back(delay_frames))
+    }
+
+    /// Linearly interpolated tap of all channels into `output`, with the same
+    /// delay convention and clamping as `RingBuffer::get_frac`.
+    pub fn tap_frac(&self, delay: f32, output: &mut [f32]) {
+        let delay = delay.max(0.0).min(self.capacity.saturating_sub(1) as f32);
+        let whole = delay as usize;
+        let frac = delay - whole as f32;
+        let newer = self.tap(whole);
+        let older = self.tap(whole + 1);
+        for ((out, &a), &b) in output.iter_mut().zip(newer).zip(older) {
+            *out = a + frac * (b - a);
+        }
+    }
+
+    fn frame(&self, index: usize) -> &[f32] {
+        &self.samples[index * self.channels..(index + 1) * self.channels]
+    }
+
+    fn index_back(&self, frames_ago: usize) -> usize {
+        (self.write_frame + self.capacity - 1 - frames_ago % self.capacity) % self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FrameRingBuffer;
+
+    #[test]
+    fn test_push_pop_frames() {
+        let mut buffer = FrameRingBuffer::new(2, 2);
+        let mut frame = [0.0; 2];
+        assert!(!buffer.pop_frame(&mut frame));
+        buffer.push_interleaved(&[1.0, -1.0, 2.0, -2.0, 3.0, -3.0]);
+        assert_eq!(buffer.len(), 2);
+        assert!(buffer.pop_frame(&mut frame));
+        assert_eq!(frame, [2.0, -2.0]);
+        assert!(buffer.pop_frame(&mut frame));
+        assert_eq!(frame, [3.0, -3.0]);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_fractional_tap() {
+        let mut buffer = FrameRingBuffer::new(2, 4);
+        for i in 0..6 {
+            buffer.push_frame(&[i as f32, -10.0 * i as f32]);
+        }
+        assert_eq!(buffer.tap(0), [5.0, -50.0]);
+        assert_eq!(buffer.tap(3), [2.0, -20.0]);
+        let mut output = [0.0; 2];
+        buffer.tap_frac(0.25, &mut output);
+        assert_eq!(output, [4.75, -47.5]);
+        buffer.tap_frac(f32::INFINITY, &mut output);
+        assert_eq!(output, [2.0, -20.0]);
+    }
+}
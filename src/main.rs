@@ -1,31 +1,236 @@
-mod ring_buffer;
-
-use std::{fs::File, io::Write};
-use hound;
+use std::{fs::File, io::{self, BufWriter, Write}};
+use ase::metering::CorrelationMeter;
+use ase::safety::SoftClipper;
+use ase::{analysis, crossfade, units};
+#[cfg(feature = "mmap")]
+use ase::mmap_wav;
 fn show_info() {
     eprintln!("MUSI-6106 Assignment Executable");
     eprintln!("(c) 2024 Stephen Garrett & Ian Clester");
 }
 
+const METER_BLOCK_FRAMES: usize = 1024;
+const DEFAULT_SPLICE_MS: f32 = 10.0;
+// Crossfaded samples above this level are soft-clipped before quantization.
+const OUTPUT_CLIP_THRESHOLD: f32 = 0.9;
+
 fn convert_sample(sample: i16) -> f32 {
     sample as f32 / i16::MAX as f32
 }
 
+fn to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16
+}
+
+// Stereo correlation over the whole file plus the worst METER_BLOCK_FRAMES
+// block, so short out-of-phase passages are not averaged away.
+struct StereoCorrelation {
+    meter: CorrelationMeter,
+    block_frames: usize,
+    worst_block: f32,
+}
+
+impl StereoCorrelation {
+    fn new() -> Self {
+        StereoCorrelation {
+            meter: CorrelationMeter::new(),
+            block_frames: 0,
+            worst_block: 1.0,
+        }
+    }
+
+    fn process_frame(&mut self, left: f32, right: f32) {
+        self.meter.process_frame(left, right);
+        self.block_frames += 1;
+        if self.block_frames == METER_BLOCK_FRAMES {
+            self.worst_block = self.worst_block.min(self.meter.finish_block());
+            self.block_frames = 0;
+        }
+    }
+
+    /// Returns (overall, worst block).
+    fn finish(mut self) -> (f32, f32) {
+        if self.block_frames > 0 {
+            self.worst_block = self.worst_block.min(self.meter.finish_block());
+        }
+        (self.meter.overall(), self.worst_block)
+    }
+}
+
+type Samples = Box<dyn Iterator<Item = hound::Result<i16>>>;
+
+fn open_with_hound(path: &str) -> hound::Result<(hound::WavSpec, Samples)> {
+    let reader = hound::WavReader::open(path)?;
+    Ok((reader.spec(), Box::new(reader.into_samples::<i16>())))
+}
+
+#[cfg(not(feature = "mmap"))]
+fn open_samples(path: &str) -> hound::Result<(hound::WavSpec, Samples)> {
+    open_with_hound(path)
+}
+
+// The mapped reader only handles 16-bit PCM; hound takes everything else.
+#[cfg(feature = "mmap")]
+fn open_samples(path: &str) -> hound::Result<(hound::WavSpec, Samples)> {
+    match mmap_wav::MappedWav::open(path) {
+        Ok(wav) => Ok((wav.spec(), Box::new(wav.into_samples().map(Ok)))),
+        Err(hound::Error::Unsupported) => open_with_hound(path),
+        Err(error) => Err(error),
+    }
+}
+
+fn write_frame(output: &mut impl Write, frame: &[f32]) -> io::Result<()> {
+    for (channel, sample) in frame.iter().enumerate() {
+        if channel > 0 {
+            output.write_all(b" ")?;
+        }
+        write!(output, "{}", sample)?;
+    }
+    writeln!(output)
+}
+
+fn analyze(path: &str) {
+    let (spec, samples) = match open_samples(path) {
+        Ok(opened) => opened,
+        Err(error) => {
+            eprintln!("Error opening file: {:?}", error);
+            return;
+        }
+    };
+
+    let num_channels = spec.channels as usize;
+    let mut mono = Vec::with_capacity(samples.size_hint().0 / num_channels);
+    let mut frame_sum = 0.0;
+    let mut correlation = StereoCorrelation::new();
+    let mut left = 0.0;
+    for (i, iterated_sample) in samples.enumerate() {
+        match iterated_sample {
+            Ok(sample) => {
+                let sample = convert_sample(sample);
+                frame_sum += sample;
+                if num_channels == 2 {
+                    if i % 2 == 0 {
+                        left = sample;
+                    } else {
+                        correlation.process_frame(left, sample);
+                    }
+                }
+                if (i + 1) % num_channels == 0 {
+                    mono.push(frame_sum / num_channels as f32);
+                    frame_sum = 0.0;
+                }
+            },
+            Err(error) => {
+                eprintln!("Error reading sample: {:?}", error);
+                return;
+            }
+        }
+    }
+
+    match analysis::estimate_modulation(&mono, spec.sample_rate as f32) {
+        Some(estimate) => {
+            println!("Center pitch: {:.2} Hz", estimate.center_hz);
+            println!("Modulation rate: {:.2} Hz", estimate.rate_hz);
+            println!("Modulation depth: {:.1} cents", estimate.depth_cents);
+        },
+        None => eprintln!("No stable pitch found in {}", path),
+    }
+    if num_channels == 2 {
+        let (overall, worst_block) = correlation.finish();
+        println!("Stereo correlation: {:.3} overall, {:.3} worst block", overall, worst_block);
+    }
+}
+
+fn read_all(path: &str) -> hound::Result<(hound::WavSpec, Vec<f32>)> {
+    let (spec, samples) = open_samples(path)?;
+    let samples = samples.map(|sample| sample.map(convert_sample)).collect::<hound::Result<_>>()?;
+    Ok((spec, samples))
+}
+
+fn splice(first_path: &str, second_path: &str, output_path: &str, overlap_ms: f32) {
+    let (spec, first) = match read_all(first_path) {
+        Ok(read) => read,
+        Err(error) => {
+            eprintln!("Error reading {}: {:?}", first_path, error);
+            return;
+        }
+    };
+    let (second_spec, second) = match read_all(second_path) {
+        Ok(read) => read,
+        Err(error) => {
+            eprintln!("Error reading {}: {:?}", second_path, error);
+            return;
+        }
+    };
+    if second_spec.channels != spec.channels || second_spec.sample_rate != spec.sample_rate {
+        eprintln!("Cannot splice files with different channel counts or sample rates");
+        return;
+    }
+
+    let overlap_frames = units::ms_to_samples(overlap_ms, spec.sample_rate as f32) as usize;
+    let channels = spec.channels as usize;
+    let mut joined = crossfade::splice(&first, &second, overlap_frames, channels, crossfade::FadeCurve::EqualPower);
+    // The equal-power fade peaks near sqrt(2) on correlated material. Only
+    // the mixed region can exceed the inputs, so leave both takes untouched
+    // outside it.
+    let overlap = first.len() + second.len() - joined.len();
+    let mixed = first.len() - overlap..first.len();
+    let clipped = SoftClipper::new(OUTPUT_CLIP_THRESHOLD).process(&mut joined[mixed]);
+    if clipped > 0 {
+        eprintln!("Soft-clipped {} samples above {}", clipped, OUTPUT_CLIP_THRESHOLD);
+    }
+
+    let mut writer = match hound::WavWriter::create(output_path, spec) {
+        Ok(writer) => writer,
+        Err(error) => {
+            eprintln!("Error creating output file: {:?}", error);
+            return;
+        }
+    };
+    for &sample in &joined {
+        if let Err(error) = writer.write_sample(to_i16(sample)) {
+            eprintln!("Error writing to output file: {:?}", error);
+            return;
+        }
+    }
+    if let Err(error) = writer.finalize() {
+        eprintln!("Error writing to output file: {:?}", error);
+    }
+}
+
 fn main() {
    show_info();
 
     // Parse command line arguments
     // First argument is input .wav file, second argument is output text file.
+    // `analyze <input.wav>` instead estimates the vibrato rate and depth.
+    // `splice <first.wav> <second.wav> <output.wav> [overlap_ms]` appends two
+    // takes with a crossfade.
     let args: Vec<String> = std::env::args().collect();
-    let mut reader = match hound::WavReader::open(&args[1]) {
-        Ok(reader) => reader,
+    if args.len() == 3 && args[1] == "analyze" {
+        analyze(&args[2]);
+        return;
+    }
+    if (args.len() == 5 || args.len() == 6) && args[1] == "splice" {
+        let overlap_ms = match args.get(5).map(|ms| ms.parse::<f32>()) {
+            None => DEFAULT_SPLICE_MS,
+            Some(Ok(ms)) if ms.is_finite() && ms >= 0.0 => ms,
+            Some(_) => {
+                eprintln!("Invalid overlap: {}", args[5]);
+                return;
+            }
+        };
+        splice(&args[2], &args[3], &args[4], overlap_ms);
+        return;
+    }
+    let (spec, samples) = match open_samples(&args[1]) {
+        Ok(opened) => opened,
         Err(error) => {
             eprintln!("Error opening file: {:?}", error);
             return;
         }
     };
 
-    let spec = reader.spec();
     dbg!(spec.bits_per_sample);
     dbg!(spec.channels);
     dbg!(spec.sample_format);
@@ -33,24 +238,24 @@ fn main() {
     let num_channels = spec.channels as usize;
 
     let mut output_file = match File::create(&args[2]) {
-        Ok(file) => file,
+        Ok(file) => BufWriter::new(file),
         Err(error) => {
             eprintln!("Error creating output file: {:?}", error);
             return;
         }
     };
     let mut frame_samples = Vec::with_capacity(num_channels);
-    for (i, iterated_sample) in reader.samples::<i16>().enumerate() {
+    let mut correlation = StereoCorrelation::new();
+    for iterated_sample in samples {
         match iterated_sample {
             Ok(sample) => {
                 frame_samples.push(convert_sample(sample));
 
                 if frame_samples.len() == num_channels {
-                    if let Err(e) = writeln!(
-                        output_file, "{}",
-                        frame_samples.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(" ")
-                    )
-                    {
+                    if num_channels == 2 {
+                        correlation.process_frame(frame_samples[0], frame_samples[1]);
+                    }
+                    if let Err(e) = write_frame(&mut output_file, &frame_samples) {
                         eprintln!("Error writing to output file: {:?}", e);
                         break;
                     }
@@ -63,4 +268,12 @@ fn main() {
             }
         }
     }
+    if let Err(e) = output_file.flush() {
+        eprintln!("Error writing to output file: {:?}", e);
+    }
+
+    if num_channels == 2 {
+        let (overall, worst_block) = correlation.finish();
+        eprintln!("Stereo correlation: {:.3} overall, {:.3} worst block", overall, worst_block);
+    }
 }
@@ -0,0 +1,110 @@
+#[derive(Clone, Copy, Default)]
+struct CorrelationSums {
+    lr: f64,
+    ll: f64,
+    rr: f64,
+}
+
+impl CorrelationSums {
+    fn add(&mut self, left: f32, right: f32) {
+        let (l, r) = (left as f64, right as f64);
+        self.lr += l * r;
+        self.ll += l * l;
+        self.rr += r * r;
+    }
+
+    fn correlation(&self) -> f32 {
+        let energy = (self.ll * self.rr).sqrt();
+        if energy <= f64::EPSILON {
+            0.0
+        } else {
+            (self.lr / energy).clamp(-1.0, 1.0) as f32
+        }
+    }
+}
+
+/// Stereo phase correlation: +1 = mono, 0 = uncorrelated, -1 = out of phase.
+/// Silence reads as 0.
+pub struct CorrelationMeter {
+    block: CorrelationSums,
+    total: CorrelationSums,
+}
+
+impl CorrelationMeter {
+    pub fn new() -> Self {
+        CorrelationMeter {
+            block: CorrelationSums::default(),
+            total: CorrelationSums::default(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.block = CorrelationSums::default();
+        self.total = CorrelationSums::default();
+    }
+
+    pub fn process_frame(&mut self, left: f32, right: f32) {
+        self.block.add(left, right);
+        self.total.add(left, right);
+    }
+
+    /// Returns the correlation of the frames seen since the last call.
+    pub fn finish_block(&mut self) -> f32 {
+        let correlation = self.block.correlation();
+        self.block = CorrelationSums::default();
+        correlation
+    }
+
+    pub fn process_block(&mut self, left: &[f32], right: &[f32]) -> f32 {
+        for (&l, &r) in left.iter().zip(right) {
+            self.process_frame(l, r);
+        }
+        self.finish_block()
+    }
+
+    pub fn overall(&self) -> f32 {
+        self.total.correlation()
+    }
+}
+
+impl Default for CorrelationMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CorrelationMeter;
+
+    #[test]
+    fn test_identical_channels() {
+        let mut meter = CorrelationMeter::new();
+        let signal: Vec<f32> = (0..64).map(|i| (i as f32 * 0.3).sin()).collect();
+        assert!((meter.process_block(&signal, &signal) - 1.0).abs() < 1e-6);
+        assert!((meter.overall() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_inverted_channels() {
+        let mut meter = CorrelationMeter::new();
+        let left: Vec<f32> = (0..64).map(|i| (i as f32 * 0.3).sin()).collect();
+        let right: Vec<f32> = left.iter().map(|s| -s).collect();
+        assert!((meter.process_block(&left, &right) + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_silence_and_overall() {
+        let mut meter = CorrelationMeter::new();
+        assert_eq!(meter.process_block(&[0.0; 16], &[0.0; 16]), 0.0);
+
+        let left = [1.0, -1.0, 1.0, -1.0];
+        meter.process_block(&left, &left);
+        let right = [-1.0, 1.0, -1.0, 1.0];
+        meter.process_block(&left, &right);
+        assert!(meter.overall().abs() < 1e-6);
+
+        meter.reset();
+        assert_eq!(meter.overall(), 0.0);
+    }
+}
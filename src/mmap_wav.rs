@@ -0,0 +1,192 @@
+use std::fs::File;
+
+use hound::{Error, SampleFormat, WavSpec};
+use memmap2::Mmap;
+
+const FORMAT_PCM: u16 = 1;
+const FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// 16-bit PCM WAV reader that decodes straight out of a memory-mapped file,
+/// so huge inputs are paged in by the OS instead of copied through a reader.
+pub struct MappedWav {
+    map: Mmap,
+    spec: WavSpec,
+    data_start: usize,
+    data_end: usize,
+}
+
+impl MappedWav {
+    pub fn open(path: &str) -> hound::Result<Self> {
+        let file = File::open(path)?;
+        // Safety: the mapping is only read. As with any mmap, the file must not
+        // be truncated by another process while it is mapped.
+        let map = unsafe { Mmap::map(&file)? };
+        let (spec, data_start, data_end) = parse_header(&map)?;
+        Ok(MappedWav {
+            map,
+            spec,
+            data_start,
+            data_end,
+        })
+    }
+
+    pub fn spec(&self) -> WavSpec {
+        self.spec
+    }
+
+    pub fn into_samples(self) -> MappedSamples {
+        MappedSamples {
+            position: self.data_start,
+            end: self.data_end,
+            map: self.map,
+        }
+    }
+}
+
+pub struct MappedSamples {
+    map: Mmap,
+    position: usize,
+    end: usize,
+}
+
+impl Iterator for MappedSamples {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        if self.position + 2 > self.end {
+            return None;
+        }
+        let sample = i16::from_le_bytes([self.map[self.position], self.map[self.position + 1]]);
+        self.position += 2;
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end - self.position) / 2;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for MappedSamples {}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+pub fn parse_header(bytes: &[u8]) -> hound::Result<(WavSpec, usize, usize)> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(Error::FormatError("no RIFF WAVE header found"));
+    }
+
+    let mut spec = None;
+    let mut offset = 12;
+    while offset + 8 <= bytes.len() {
+        let chunk_id = &bytes[offset..offset + 4];
+        let chunk_len = read_u32(bytes, offset + 4) as usize;
+        let body = offset + 8;
+        match chunk_id {
+            b"fmt " => {
+                if chunk_len < 16 || body + chunk_len > bytes.len() {
+                    return Err(Error::FormatError("invalid fmt chunk"));
+                }
+                let mut format = read_u16(bytes, body);
+                if format == FORMAT_EXTENSIBLE {
+                    if chunk_len < 40 {
+                        return Err(Error::FormatError("invalid extensible fmt chunk"));
+                    }
+                    format = read_u16(bytes, body + 24);
+                }
+                let bits_per_sample = read_u16(bytes, body + 14);
+                if format != FORMAT_PCM || bits_per_sample != 16 {
+                    return Err(Error::Unsupported);
+                }
+                let channels = read_u16(bytes, body + 2);
+                let sample_rate = read_u32(bytes, body + 4);
+                if channels == 0 {
+                    return Err(Error::FormatError("file contains zero channels"));
+                }
+                if sample_rate == 0 {
+                    return Err(Error::FormatError("sample rate is zero"));
+                }
+                spec = Some(WavSpec {
+                    channels,
+                    sample_rate,
+                    bits_per_sample,
+                    sample_format: SampleFormat::Int,
+                });
+            }
+            b"data" => {
+                let spec = spec.ok_or(Error::FormatError("data chunk before fmt chunk"))?;
+                let end = (body + chunk_len).min(bytes.len());
+                return Ok((spec, body, end));
+            }
+            _ => {}
+        }
+        offset = body + chunk_len + (chunk_len & 1);
+    }
+    Err(Error::FormatError("no data chunk found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_header, MappedWav};
+
+    fn write_wav(path: &std::path::Path, channels: u16, samples: &[i16]) {
+        let spec = hound::WavSpec {
+            channels,
+            sample_rate: 48000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for &sample in samples {
+            writer.write_sample(sample).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    #[test]
+    fn test_matches_hound() {
+        let path = std::env::temp_dir().join("ase_mmap_wav_matches_hound.wav");
+        let samples: Vec<i16> = (0..1000).map(|i| (i * 37 % 65536 - 32768) as i16).collect();
+        write_wav(&path, 2, &samples);
+
+        let wav = MappedWav::open(path.to_str().unwrap()).unwrap();
+        assert_eq!(wav.spec(), hound::WavReader::open(&path).unwrap().spec());
+        let mapped = wav.into_samples();
+        assert_eq!(mapped.len(), samples.len());
+        assert_eq!(mapped.collect::<Vec<_>>(), samples);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_zero_channels_and_rate() {
+        let mut header = Vec::new();
+        header.extend_from_slice(b"RIFF\0\0\0\0WAVEfmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&1u16.to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(&48000u32.to_le_bytes());
+        header.extend_from_slice(&[0; 6]);
+        header.extend_from_slice(&16u16.to_le_bytes());
+        header.extend_from_slice(b"data\0\0\0\0");
+        assert!(parse_header(&header).is_err());
+
+        header[22] = 2;
+        assert!(parse_header(&header).is_ok());
+        header[24..28].copy_from_slice(&0u32.to_le_bytes());
+        assert!(parse_header(&header).is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_wav() {
+        let path = std::env::temp_dir().join("ase_mmap_wav_rejects_non_wav.wav");
+        std::fs::write(&path, b"definitely not a wav file").unwrap();
+        assert!(MappedWav::open(path.to_str().unwrap()).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
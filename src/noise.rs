@@ -0,0 +1,157 @@
+const DEFAULT_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Small xorshift64* generator: fast, seedable and identical on every platform,
+/// so noise-based renders and tests are reproducible.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on an all-zero state.
+        Rng {
+            state: if seed == 0 { DEFAULT_SEED } else { seed },
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform in [0, 1).
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Uniform in [-1, 1).
+    pub fn next_bipolar(&mut self) -> f32 {
+        2.0 * self.next_f32() - 1.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoiseColor {
+    White,
+    Pink,
+    Brown,
+}
+
+pub struct NoiseGenerator {
+    rng: Rng,
+    color: NoiseColor,
+    pink_state: [f32; 7],
+    brown_state: f32,
+}
+
+impl NoiseGenerator {
+    pub fn new(color: NoiseColor, seed: u64) -> Self {
+        NoiseGenerator {
+            rng: Rng::new(seed),
+            color,
+            pink_state: [0.0; 7],
+            brown_state: 0.0,
+        }
+    }
+
+    /// Restarts the sequence from `seed` and clears the coloring filters.
+    pub fn reset(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+        self.pink_state = [0.0; 7];
+        self.brown_state = 0.0;
+    }
+
+    pub fn color(&self) -> NoiseColor {
+        self.color
+    }
+
+    pub fn set_color(&mut self, color: NoiseColor) {
+        self.color = color;
+    }
+
+    pub fn next_sample(&mut self) -> f32 {
+        let white = self.rng.next_bipolar();
+        match self.color {
+            NoiseColor::White => white,
+            NoiseColor::Pink => {
+                // Paul Kellet's refined pink filter.
+                let b = &mut self.pink_state;
+                b[0] = 0.99886 * b[0] + white * 0.0555179;
+                b[1] = 0.99332 * b[1] + white * 0.0750759;
+                b[2] = 0.96900 * b[2] + white * 0.153852;
+                b[3] = 0.86650 * b[3] + white * 0.3104856;
+                b[4] = 0.55000 * b[4] + white * 0.5329522;
+                b[5] = -0.7616 * b[5] - white * 0.0168980;
+                let pink = b[0] + b[1] + b[2] + b[3] + b[4] + b[5] + b[6] + white * 0.5362;
+                b[6] = white * 0.115926;
+                pink * 0.11
+            }
+            NoiseColor::Brown => {
+                self.brown_state = (self.brown_state + 0.02 * white) / 1.02;
+                self.brown_state * 3.5
+            }
+        }
+    }
+
+    pub fn fill(&mut self, output: &mut [f32]) {
+        for sample in output.iter_mut() {
+            *sample = self.next_sample();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NoiseColor, NoiseGenerator, Rng};
+
+    // Energy of the first difference relative to the signal energy; lower
+    // means less high-frequency content.
+    fn brightness(samples: &[f32]) -> f32 {
+        let energy: f32 = samples.iter().map(|s| s * s).sum();
+        let diff: f32 = samples.windows(2).map(|w| (w[1] - w[0]).powi(2)).sum();
+        diff / energy
+    }
+
+    fn render(color: NoiseColor, seed: u64) -> Vec<f32> {
+        let mut generator = NoiseGenerator::new(color, seed);
+        let mut output = vec![0.0; 8192];
+        generator.fill(&mut output);
+        output
+    }
+
+    #[test]
+    fn test_seeded_output_is_deterministic() {
+        assert_eq!(render(NoiseColor::Pink, 42), render(NoiseColor::Pink, 42));
+        assert_ne!(render(NoiseColor::White, 1), render(NoiseColor::White, 2));
+
+        let mut generator = NoiseGenerator::new(NoiseColor::Brown, 7);
+        let first: Vec<f32> = (0..100).map(|_| generator.next_sample()).collect();
+        generator.reset(7);
+        let second: Vec<f32> = (0..100).map(|_| generator.next_sample()).collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_rng_range() {
+        let mut rng = Rng::new(0);
+        for _ in 0..10000 {
+            let value = rng.next_f32();
+            assert!((0.0..1.0).contains(&value));
+            let bipolar = rng.next_bipolar();
+            assert!((-1.0..1.0).contains(&bipolar));
+        }
+    }
+
+    #[test]
+    fn test_colors_tilt_spectrum() {
+        let white = brightness(&render(NoiseColor::White, 3));
+        let pink = brightness(&render(NoiseColor::Pink, 3));
+        let brown = brightness(&render(NoiseColor::Brown, 3));
+        assert!(white > pink && pink > brown);
+        assert!(render(NoiseColor::Pink, 3).iter().all(|s| s.abs() <= 1.0));
+        assert!(render(NoiseColor::Brown, 3).iter().all(|s| s.abs() <= 1.0));
+    }
+}
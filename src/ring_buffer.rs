@@ -1,22 +1,48 @@
+use alloc::{vec, vec::Vec};
+use core::iter::Chain;
+use core::ops::{Index, IndexMut};
+use core::slice;
+
+/// Borrowing iterator over a `RingBuffer`, oldest value first.
+pub type Iter<'a, T> = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+/// Mutable iterator over a `RingBuffer`, oldest value first.
+pub type IterMut<'a, T> = Chain<slice::IterMut<'a, T>, slice::IterMut<'a, T>>;
+
+/// Shortest delay `RingBuffer::get_frac_allpass` reads at, in samples. It
+/// keeps the allpass coefficient at or below 0.9 / 1.1.
+pub const ALLPASS_MIN_OFFSET: f32 = 0.1;
+
+/// Fixed-capacity circular buffer. `push` overwrites the oldest value once
+/// the buffer is full.
 pub struct RingBuffer<T> {
     buffer: Vec<T>,
     read_index: usize,
     write_index: usize,
     capacity: usize,
+    // `capacity - 1` when the capacity is a power of two, so wrapping is a
+    // bitmask instead of a division.
+    mask: Option<usize>,
     size: usize,
 }
 
-impl<T: Copy + Default> RingBuffer<T> {
+impl<T: Clone + Default> RingBuffer<T> {
     pub fn new(length: usize) -> Self {
         RingBuffer {
             buffer: vec![T::default(); length],
             read_index: 0,
             write_index: 0,
             capacity: length,
+            mask: length.is_power_of_two().then(|| length - 1),
             size: 0,
         }
     }
 
+    /// Rounds `min_length` up to the next power of two so every index wrap
+    /// is a bitmask, which matters in per-sample delay-line loops.
+    pub fn new_power_of_two(min_length: usize) -> Self {
+        Self::new(min_length.max(1).next_power_of_two())
+    }
+
     pub fn reset(&mut self) {
         self.read_index = 0;
         self.write_index = 0;
@@ -24,13 +50,38 @@ impl<T: Copy + Default> RingBuffer<T> {
         self.buffer.fill(T::default());
     }
 
+    /// Reallocates to `new_capacity`, keeping the most recent values in
+    /// order. When shrinking below `len()`, the oldest values are dropped.
+    pub fn resize(&mut self, new_capacity: usize) {
+        let kept = self.size.min(new_capacity);
+        let mut buffer = Vec::with_capacity(new_capacity);
+        let (older, newer) = self.as_slices();
+        let skipped = self.size - kept;
+        if skipped < older.len() {
+            buffer.extend_from_slice(&older[skipped..]);
+            buffer.extend_from_slice(newer);
+        } else {
+            buffer.extend_from_slice(&newer[skipped - older.len()..]);
+        }
+        buffer.resize(new_capacity, T::default());
+
+        self.buffer = buffer;
+        self.capacity = new_capacity;
+        self.mask = new_capacity.is_power_of_two().then(|| new_capacity - 1);
+        self.read_index = 0;
+        self.size = kept;
+        self.write_index = if new_capacity == 0 { 0 } else { self.wrap(kept) };
+    }
+
+    /// Writes at the write index without advancing it.
     pub fn put(&mut self, value: T) {
         self.buffer[self.write_index] = value;
         
     }
 
+    /// Value at the read index, without removing it.
     pub fn peek(&self) -> T {
-        self.buffer[self.read_index]
+        self.buffer[self.read_index].clone()
     }
 
 
@@ -40,33 +91,111 @@ impl<T: Copy + Default> RingBuffer<T> {
 
         
         if self.size == self.capacity {
-            self.read_index = (self.read_index + 1) % self.capacity;
+            self.read_index = self.wrap(self.read_index + 1);
         } else {
             
             self.size += 1;
         }
 
         
-        self.write_index = (self.write_index + 1) % self.capacity;
+        self.write_index = self.wrap(self.write_index + 1);
     }
 
+    /// Like `push`, but hands the value back instead of overwriting when the
+    /// buffer is full, for callers that need backpressure.
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        if self.size == self.capacity {
+            return Err(value);
+        }
+        self.push(value);
+        Ok(())
+    }
+
+    /// Removes the oldest value, or returns `T::default()` when empty.
     pub fn pop(&mut self) -> T {
         if self.size == 0 {
             
             T::default()
         } else {
             
-            let value = self.buffer[self.read_index];
-            self.read_index = (self.read_index + 1) % self.capacity;
+            let value = self.buffer[self.read_index].clone();
+            self.read_index = self.wrap(self.read_index + 1);
             self.size -= 1; 
             value
         }
     }
 
+    /// Pushes `count` default values (silence for samples) with at most two
+    /// slice fills, for latency padding or flushing a delay tail. Overwrites
+    /// the oldest values like `push`.
+    pub fn push_zeros(&mut self, count: usize) {
+        let skipped = count.saturating_sub(self.capacity);
+        let count = count - skipped;
+        let start = self.wrap(self.write_index + self.wrap(skipped));
+        let first = count.min(self.capacity - start);
+        self.buffer[start..start + first].fill(T::default());
+        self.buffer[..count - first].fill(T::default());
+
+        self.write_index = self.wrap(start + count);
+        self.size = self.size.saturating_add(skipped + count).min(self.capacity);
+        if self.size == self.capacity {
+            self.read_index = self.write_index;
+        }
+    }
+
+    /// Pops up to `count` values, oldest first, as they are iterated. Values
+    /// the iterator does not reach stay in the buffer.
+    pub fn drain(&mut self, count: usize) -> Drain<'_, T> {
+        let remaining = count.min(self.size);
+        Drain {
+            buffer: self,
+            remaining,
+        }
+    }
+
+    /// Buffered values from oldest to newest as two contiguous slices, like
+    /// `VecDeque::as_slices`. The second slice is empty unless the values wrap
+    /// around the end of the storage.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let first = self.size.min(self.capacity - self.read_index);
+        let (wrapped, head) = self.buffer.split_at(self.read_index);
+        (&head[..first], &wrapped[..self.size - first])
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let first = self.size.min(self.capacity - self.read_index);
+        let (wrapped, head) = self.buffer.split_at_mut(self.read_index);
+        (&mut head[..first], &mut wrapped[..self.size - first])
+    }
+
+    /// Iterates over the buffered values from oldest to newest.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (older, newer) = self.as_slices();
+        older.iter().chain(newer)
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (older, newer) = self.as_mut_slices();
+        older.iter_mut().chain(newer)
+    }
+
+    /// Value `offset` places after the read index, wrapping around the capacity.
     pub fn get(&self, offset: usize) -> T {
         
-        let index = (self.read_index + offset) % self.capacity;
-        self.buffer[index]
+        let index = self.wrap(self.read_index + offset);
+        self.buffer[index].clone()
+    }
+
+    /// Value pushed `delay_samples` pushes before the newest one, independent
+    /// of the read index: 0 is the newest value. Delays wrap around the
+    /// capacity.
+    pub fn tap(&self, delay_samples: usize) -> T {
+        self.buffer[self.index_back(delay_samples)].clone()
+    }
+
+    /// Like `get`, but `None` unless `offset < len()` instead of wrapping.
+    pub fn try_get(&self, offset: usize) -> Option<T> {
+        (offset < self.size).then(|| self.get(offset))
     }
 
 
@@ -74,8 +203,14 @@ impl<T: Copy + Default> RingBuffer<T> {
         self.read_index
     }
 
+    /// Moving either index redefines the buffered region as read..write, so
+    /// the size is recomputed to keep len() consistent with the indices.
     pub fn set_read_index(&mut self, index: usize) {
-        self.read_index = index % self.capacity;
+        let index = self.wrap(index);
+        if index != self.read_index {
+            self.read_index = index;
+            self.size = self.wrap(self.write_index + self.capacity - index);
+        }
     }
 
     pub fn get_write_index(&self) -> usize {
@@ -83,21 +218,425 @@ impl<T: Copy + Default> RingBuffer<T> {
     }
 
     pub fn set_write_index(&mut self, index: usize) {
-        self.write_index = index % self.capacity;
+        let index = self.wrap(index);
+        if index != self.write_index {
+            self.write_index = index;
+            self.size = self.wrap(index + self.capacity - self.read_index);
+        }
     }
 
     pub fn len(&self) -> usize {
         self.size
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.size == self.capacity
+    }
+
     pub fn capacity(&self) -> usize {
         self.capacity
     }
+
+    /// Snapshot of the whole storage and both indices, enough to restore
+    /// delay-line history exactly.
+    pub fn save_state(&self) -> RingBufferState<T> {
+        RingBufferState {
+            contents: self.buffer.clone(),
+            read_index: self.read_index,
+            write_index: self.write_index,
+            len: self.size,
+        }
+    }
+
+    /// Restores a snapshot, taking over its capacity. Returns false and
+    /// leaves the buffer untouched if the state is inconsistent.
+    pub fn load_state(&mut self, state: &RingBufferState<T>) -> bool {
+        let capacity = state.contents.len();
+        let valid = if capacity == 0 {
+            state.read_index == 0 && state.write_index == 0 && state.len == 0
+        } else {
+            state.read_index < capacity
+                && state.write_index < capacity
+                && state.len <= capacity
+                && (state.read_index + state.len) % capacity == state.write_index
+        };
+        if !valid {
+            return false;
+        }
+        *self = RingBuffer {
+            buffer: state.contents.clone(),
+            read_index: state.read_index,
+            write_index: state.write_index,
+            capacity,
+            mask: capacity.is_power_of_two().then(|| capacity - 1),
+            size: state.len,
+        };
+        true
+    }
+
+    fn wrap(&self, index: usize) -> usize {
+        match self.mask {
+            Some(mask) => index & mask,
+            None => index % self.capacity,
+        }
+    }
+
+    // Buffer index of the value written `samples_ago` pushes before the
+    // newest one.
+    fn index_back(&self, samples_ago: usize) -> usize {
+        self.wrap(self.write_index + self.capacity - 1 - self.wrap(samples_ago))
+    }
+}
+
+// Bulk copies are only offered for `Copy` values, where they compile down
+// to memcpy.
+impl<T: Copy + Default> RingBuffer<T> {
+    /// Pushes all of `values` with at most two slice copies. As with `push`,
+    /// the oldest values are overwritten once the buffer is full.
+    pub fn write_from(&mut self, values: &[T]) {
+        let skipped = values.len().saturating_sub(self.capacity);
+        let values = &values[skipped..];
+        let start = self.wrap(self.write_index + self.wrap(skipped));
+        let first = values.len().min(self.capacity - start);
+        self.buffer[start..start + first].copy_from_slice(&values[..first]);
+        self.buffer[..values.len() - first].copy_from_slice(&values[first..]);
+
+        self.write_index = self.wrap(start + values.len());
+        self.size = self.size.saturating_add(skipped + values.len()).min(self.capacity);
+        if self.size == self.capacity {
+            self.read_index = self.write_index;
+        }
+    }
+
+    /// Pops up to `output.len()` values into `output` with at most two slice
+    /// copies and returns how many were read.
+    pub fn read_into(&mut self, output: &mut [T]) -> usize {
+        let count = output.len().min(self.size);
+        let first = count.min(self.capacity - self.read_index);
+        output[..first].copy_from_slice(&self.buffer[self.read_index..self.read_index + first]);
+        output[first..count].copy_from_slice(&self.buffer[..count - first]);
+
+        self.read_index = self.wrap(self.read_index + count);
+        self.size -= count;
+        count
+    }
+}
+
+/// Indexes relative to the read index like `get`, but panics when the
+/// offset is not below `len()`.
+impl<T: Clone + Default> Index<usize> for RingBuffer<T> {
+    type Output = T;
+
+    fn index(&self, offset: usize) -> &T {
+        assert!(offset < self.size, "offset {} out of range for length {}", offset, self.size);
+        &self.buffer[self.wrap(self.read_index + offset)]
+    }
+}
+
+impl<T: Clone + Default> IndexMut<usize> for RingBuffer<T> {
+    fn index_mut(&mut self, offset: usize) -> &mut T {
+        assert!(offset < self.size, "offset {} out of range for length {}", offset, self.size);
+        let index = self.wrap(self.read_index + offset);
+        &mut self.buffer[index]
+    }
+}
+
+/// Pushes every value, overwriting the oldest ones like `push`.
+impl<T: Clone + Default> Extend<T> for RingBuffer<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, values: I) {
+        for value in values {
+            self.push(value);
+        }
+    }
+}
+
+impl<'a, T: Clone + Default> IntoIterator for &'a RingBuffer<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: Clone + Default> IntoIterator for &'a mut RingBuffer<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T: Clone + Default> IntoIterator for RingBuffer<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { buffer: self }
+    }
+}
+
+/// Owning iterator that pops a `RingBuffer` from oldest to newest.
+pub struct IntoIter<T> {
+    buffer: RingBuffer<T>,
+}
+
+impl<T: Clone + Default> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.buffer.is_empty() {
+            None
+        } else {
+            Some(self.buffer.pop())
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.buffer.len(), Some(self.buffer.len()))
+    }
+}
+
+impl<T: Clone + Default> ExactSizeIterator for IntoIter<T> {}
+
+/// Iterator returned by `RingBuffer::drain`.
+pub struct Drain<'a, T> {
+    buffer: &'a mut RingBuffer<T>,
+    remaining: usize,
+}
+
+impl<T: Clone + Default> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some(self.buffer.pop())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T: Clone + Default> ExactSizeIterator for Drain<'_, T> {}
+
+impl RingBuffer<f32> {
+    /// Linearly interpolated read `offset` samples back from the write index:
+    /// 0.0 is the newest value, `capacity - 1` the oldest. Offsets outside
+    /// that range read the newest or oldest value.
+    pub fn get_frac(&self, offset: f32) -> f32 {
+        let offset = self.clamp_offset(offset, 0);
+        let whole = offset as usize;
+        let frac = offset - whole as f32;
+        let newer = self.buffer[self.index_back(whole)];
+        let older = self.buffer[self.index_back(whole + 1)];
+        newer + frac * (older - newer)
+    }
+
+    /// Fractional counterpart of `tap`; same as `get_frac`.
+    pub fn tap_frac(&self, delay: f32) -> f32 {
+        self.get_frac(delay)
+    }
+
+    /// `get_frac` for several delays at once, e.g. the voices of a chorus.
+    /// Reads `min(delays.len(), output.len())` taps. With the `simd` feature
+    /// the interpolation runs eight taps at a time.
+    pub fn tap_frac_multi(&self, delays: &[f32], output: &mut [f32]) {
+        let count = delays.len().min(output.len());
+        let (delays, output) = (&delays[..count], &mut output[..count]);
+        #[cfg(feature = "simd")]
+        let (delays, output) = {
+            use wide::f32x8;
+
+            let mut delay_chunks = delays.chunks_exact(8);
+            let mut output_chunks = output.chunks_exact_mut(8);
+            for (delays, output) in (&mut delay_chunks).zip(&mut output_chunks) {
+                let (mut newer, mut older, mut frac) = ([0.0; 8], [0.0; 8], [0.0; 8]);
+                for lane in 0..8 {
+                    let delay = self.clamp_offset(delays[lane], 0);
+                    let whole = delay as usize;
+                    frac[lane] = delay - whole as f32;
+                    newer[lane] = self.buffer[self.index_back(whole)];
+                    older[lane] = self.buffer[self.index_back(whole + 1)];
+                }
+                let newer = f32x8::from(newer);
+                let value = newer + f32x8::from(frac) * (f32x8::from(older) - newer);
+                output.copy_from_slice(&value.to_array());
+            }
+            (delay_chunks.remainder(), output_chunks.into_remainder())
+        };
+        for (out, &delay) in output.iter_mut().zip(delays) {
+            *out = self.get_frac(delay);
+        }
+    }
+
+    /// Like `get_frac`, but with 4-point Catmull-Rom interpolation, which
+    /// keeps more high-frequency content than linear interpolation. The two
+    /// older neighbours must be in the buffer, so offsets are limited to
+    /// `capacity - 3`.
+    pub fn get_frac_cubic(&self, offset: f32) -> f32 {
+        let offset = self.clamp_offset(offset, 2);
+        let whole = offset as usize;
+        let frac = offset - whole as f32;
+        let p0 = self.buffer[self.index_back(whole.saturating_sub(1))];
+        let p1 = self.buffer[self.index_back(whole)];
+        let p2 = self.buffer[self.index_back(whole + 1)];
+        let p3 = self.buffer[self.index_back(whole + 2)];
+
+        let c1 = 0.5 * (p2 - p0);
+        let c2 = p0 - 2.5 * p1 + 2.0 * p2 - 0.5 * p3;
+        let c3 = 0.5 * (p3 - p0) + 1.5 * (p1 - p2);
+        ((c3 * frac + c2) * frac + c1) * frac + p1
+    }
+
+    /// First-order allpass interpolated read: flat magnitude response at the
+    /// cost of a slightly frequency-dependent delay. The filter is recursive,
+    /// so call it exactly once per pushed sample with the reader's own
+    /// `state`. Offsets below `ALLPASS_MIN_OFFSET` read at that offset: a
+    /// shorter fractional delay moves the filter pole onto the unit circle,
+    /// where it rings forever.
+    pub fn get_frac_allpass(&self, offset: f32, state: &mut AllpassState) -> f32 {
+        let offset = self.clamp_offset(offset.max(ALLPASS_MIN_OFFSET), 0);
+        let mut whole = offset as usize;
+        let mut frac = offset - whole as f32;
+        // Keep the fractional part in [0.1, 1.1) where the allpass behaves best.
+        if frac < ALLPASS_MIN_OFFSET && whole > 0 {
+            whole -= 1;
+            frac += 1.0;
+        }
+        let coefficient = (1.0 - frac) / (1.0 + frac);
+        let newer = self.buffer[self.index_back(whole)];
+        let older = self.buffer[self.index_back(whole + 1)];
+        let output = coefficient * newer + older - coefficient * state.previous_output;
+        state.previous_output = output;
+        output
+    }
+
+    /// Windowed-sinc read using the precomputed kernel in `table`. Taps that
+    /// would lie after the newest sample are clamped to it, and offsets are
+    /// limited so the oldest tap stays in the buffer.
+    #[cfg(feature = "std")]
+    pub fn get_frac_sinc(&self, offset: f32, table: &SincTable) -> f32 {
+        let offset = self.clamp_offset(offset, table.taps / 2);
+        let whole = offset as usize;
+        let position = (offset - whole as f32) * table.phases as f32;
+        let phase = (position as usize).min(table.phases - 1);
+        let blend = position - phase as f32;
+        let lower = table.row(phase);
+        let upper = table.row(phase + 1);
+
+        let newest_tap = table.taps / 2 - 1;
+        let mut sum = 0.0;
+        for j in 0..table.taps {
+            let samples_ago = (whole + j).saturating_sub(newest_tap);
+            let coefficient = lower[j] + blend * (upper[j] - lower[j]);
+            sum += coefficient * self.buffer[self.index_back(samples_ago)];
+        }
+        sum
+    }
+
+    // Limits a fractional offset to [0, capacity - 1 - older_taps], where
+    // older_taps is how many extra older samples the interpolator reads past
+    // the whole part. That keeps every tap on the right side of the wrap, and
+    // huge or infinite offsets cannot overflow the index math. NaN reads as 0.
+    fn clamp_offset(&self, offset: f32, older_taps: usize) -> f32 {
+        offset.max(0.0).min(self.capacity.saturating_sub(1 + older_taps) as f32)
+    }
+}
+
+/// Saved contents and indices of a `RingBuffer`, from `save_state`. The
+/// fields are public so callers can serialize them in any format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RingBufferState<T> {
+    /// The full storage, `capacity` values long, in storage order.
+    pub contents: Vec<T>,
+    pub read_index: usize,
+    pub write_index: usize,
+    pub len: usize,
+}
+
+/// Filter memory for one `RingBuffer::get_frac_allpass` reader, e.g. one per
+/// channel or tap.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllpassState {
+    previous_output: f32,
+}
+
+impl AllpassState {
+    pub fn new() -> Self {
+        AllpassState::default()
+    }
+
+    pub fn reset(&mut self) {
+        self.previous_output = 0.0;
+    }
+}
+
+/// Polyphase Blackman-windowed sinc kernel for `RingBuffer::get_frac_sinc`.
+#[cfg(feature = "std")]
+pub struct SincTable {
+    taps: usize,
+    phases: usize,
+    coefficients: Vec<f32>,
+}
+
+#[cfg(feature = "std")]
+impl SincTable {
+    const PHASES: usize = 512;
+
+    /// `taps` is rounded up to an even number, at least 2 (8, 16 or 32 are
+    /// typical).
+    pub fn new(taps: usize) -> Self {
+        use core::f32::consts::PI;
+
+        let taps = (taps.max(2) + 1) & !1;
+        let phases = Self::PHASES;
+        let half = (taps / 2) as f32;
+        let mut coefficients = Vec::with_capacity((phases + 1) * taps);
+        for phase in 0..=phases {
+            let frac = phase as f32 / phases as f32;
+            let row_start = coefficients.len();
+            for j in 0..taps {
+                let t = j as f32 - (half - 1.0) - frac;
+                let sinc = if t == 0.0 { 1.0 } else { (PI * t).sin() / (PI * t) };
+                let x = (t / half).clamp(-1.0, 1.0);
+                let window = 0.42 + 0.5 * (PI * x).cos() + 0.08 * (2.0 * PI * x).cos();
+                coefficients.push(sinc * window);
+            }
+            let sum: f32 = coefficients[row_start..].iter().sum();
+            coefficients[row_start..].iter_mut().for_each(|c| *c /= sum);
+        }
+        SincTable {
+            taps,
+            phases,
+            coefficients,
+        }
+    }
+
+    pub fn taps(&self) -> usize {
+        self.taps
+    }
+
+    fn row(&self, phase: usize) -> &[f32] {
+        &self.coefficients[phase * self.taps..(phase + 1) * self.taps]
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::RingBuffer;
+    use super::{AllpassState, RingBuffer, RingBufferState};
+    #[cfg(feature = "std")]
+    use super::SincTable;
+    use proptest::prelude::*;
+    use std::collections::VecDeque;
 
     #[test]
     fn test_initialization_and_capacity() {
@@ -170,4 +709,457 @@ mod tests {
         assert_eq!(buffer.get(0), 1); 
         assert_eq!(buffer.get(4), 5); 
     }
+
+    #[test]
+    fn test_set_index_updates_size() {
+        let mut buffer: RingBuffer<f32> = RingBuffer::new(4);
+        buffer.set_write_index(3);
+        assert_eq!(buffer.len(), 3);
+        buffer.set_read_index(1);
+        assert_eq!(buffer.len(), 2);
+        buffer.set_read_index(3);
+        assert_eq!(buffer.len(), 0);
+    }
+
+    #[test]
+    fn test_power_of_two_capacity() {
+        assert_eq!(RingBuffer::<f32>::new_power_of_two(0).capacity(), 1);
+        assert_eq!(RingBuffer::<f32>::new_power_of_two(8).capacity(), 8);
+        let mut buffer = RingBuffer::new_power_of_two(5);
+        assert_eq!(buffer.capacity(), 8);
+        for i in 0..11 {
+            buffer.push(i);
+        }
+        assert_eq!(buffer.len(), 8);
+        assert_eq!(buffer.get_write_index(), 3);
+        assert_eq!(buffer.get(0), 3);
+        assert_eq!(buffer.get(9), 4);
+        assert_eq!(buffer.pop(), 3);
+    }
+
+    #[test]
+    fn test_resize_keeps_newest_values() {
+        let mut buffer = RingBuffer::new(4);
+        for i in 0..6 {
+            buffer.push(i);
+        }
+        buffer.resize(6);
+        assert_eq!(buffer.capacity(), 6);
+        assert_eq!(buffer.as_slices(), (&[2, 3, 4, 5][..], &[][..]));
+        buffer.push(6);
+        buffer.push(7);
+        buffer.push(8);
+        assert_eq!(buffer.pop(), 3);
+
+        buffer.resize(3);
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.pop(), 6);
+        assert_eq!(buffer.pop(), 7);
+        assert_eq!(buffer.pop(), 8);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut buffer = RingBuffer::new(3);
+        for i in 0..5 {
+            buffer.push(i);
+        }
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), [2, 3, 4]);
+        for value in &mut buffer {
+            *value *= 10;
+        }
+        assert_eq!((&buffer).into_iter().rev().copied().collect::<Vec<_>>(), [40, 30, 20]);
+        let owned = buffer.into_iter();
+        assert_eq!(owned.len(), 3);
+        assert_eq!(owned.collect::<Vec<_>>(), [20, 30, 40]);
+    }
+
+    #[test]
+    fn test_index_and_try_get() {
+        let mut buffer = RingBuffer::new(3);
+        for i in 0..4 {
+            buffer.push(i);
+        }
+        assert_eq!(buffer[0], 1);
+        assert_eq!(buffer[2], 3);
+        buffer[1] = 20;
+        assert_eq!(buffer.try_get(1), Some(20));
+        assert_eq!(buffer.try_get(3), None);
+        buffer.pop();
+        assert_eq!(buffer.try_get(2), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_range() {
+        let mut buffer = RingBuffer::new(3);
+        buffer.push(1);
+        let _ = buffer[1];
+    }
+
+    #[test]
+    fn test_try_push() {
+        let mut buffer = RingBuffer::new(2);
+        assert_eq!(buffer.try_push(1), Ok(()));
+        assert_eq!(buffer.try_push(2), Ok(()));
+        assert!(buffer.is_full());
+        assert_eq!(buffer.try_push(3), Err(3));
+        assert_eq!(buffer.pop(), 1);
+        assert_eq!(buffer.try_push(3), Ok(()));
+        assert_eq!(buffer.pop(), 2);
+        assert_eq!(buffer.pop(), 3);
+    }
+
+    #[test]
+    fn test_tap_ignores_read_index() {
+        let mut buffer = RingBuffer::new(4);
+        for i in 0..6 {
+            buffer.push(i as f32);
+        }
+        buffer.pop();
+        buffer.pop();
+        assert_eq!(buffer.tap(0), 5.0);
+        assert_eq!(buffer.tap(3), 2.0);
+        assert_eq!(buffer.tap_frac(1.5), 3.5);
+    }
+
+    #[test]
+    fn test_non_copy_values() {
+        let mut buffer: RingBuffer<String> = RingBuffer::new(2);
+        buffer.push("a".to_string());
+        buffer.push("b".to_string());
+        buffer.push("c".to_string());
+        assert_eq!(buffer.peek(), "b");
+        assert_eq!(buffer.tap(0), "c");
+        assert_eq!(buffer.iter().cloned().collect::<Vec<_>>(), ["b", "c"]);
+        assert_eq!(buffer.pop(), "b");
+        assert_eq!(buffer.pop(), "c");
+        assert_eq!(buffer.pop(), "");
+    }
+
+    #[test]
+    fn test_push_zeros() {
+        let mut buffer = RingBuffer::new(4);
+        buffer.extend([1, 2, 3]);
+        buffer.push_zeros(2);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), [2, 3, 0, 0]);
+        assert_eq!(buffer.get_write_index(), 1);
+        buffer.push(5);
+        buffer.push_zeros(9);
+        assert_eq!(buffer.len(), 4);
+        assert_eq!(buffer.get_write_index(), 3);
+        assert!(buffer.iter().all(|&value| value == 0));
+
+        let mut buffer = RingBuffer::new(3);
+        buffer.extend([1, 2]);
+        buffer.push_zeros(usize::MAX);
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.get_read_index(), buffer.get_write_index());
+        assert!(buffer.iter().all(|&value| value == 0));
+    }
+
+    #[test]
+    fn test_save_and_load_state() {
+        let mut buffer = RingBuffer::new(4);
+        buffer.extend([1.0, 2.0, 3.0, 4.0, 5.0]);
+        buffer.pop();
+        let state = buffer.save_state();
+
+        let mut restored = RingBuffer::new(16);
+        assert!(restored.load_state(&state));
+        assert_eq!(restored.capacity(), 4);
+        assert_eq!(restored.save_state(), state);
+        assert_eq!(restored.tap(3), 2.0);
+        for _ in 0..3 {
+            assert_eq!(restored.pop(), buffer.pop());
+        }
+
+        let broken = RingBufferState { len: 2, ..state };
+        assert!(!restored.load_state(&broken));
+        assert_eq!(restored.capacity(), 4);
+    }
+
+    #[test]
+    fn test_extend_and_drain() {
+        let mut buffer = RingBuffer::new(4);
+        buffer.extend(0..6);
+        assert_eq!(buffer.len(), 4);
+        let drained = buffer.drain(3);
+        assert_eq!(drained.len(), 3);
+        assert_eq!(drained.collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!(buffer.drain(10).collect::<Vec<_>>(), [5]);
+
+        buffer.extend([7, 8, 9]);
+        assert_eq!(buffer.drain(2).next(), Some(7));
+        assert_eq!(buffer.len(), 2);
+    }
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Push(i32),
+        Pop,
+        SetReadIndex(usize),
+        SetWriteIndex(usize),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            4 => any::<i32>().prop_map(Op::Push),
+            3 => Just(Op::Pop),
+            1 => any::<usize>().prop_map(Op::SetReadIndex),
+            1 => any::<usize>().prop_map(Op::SetWriteIndex),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_fifo_model(capacity in 1usize..16, values in prop::collection::vec(any::<Option<i32>>(), 0..200)) {
+            let mut buffer = RingBuffer::new(capacity);
+            let mut model = VecDeque::new();
+            for value in values {
+                match value {
+                    Some(v) => {
+                        if model.len() == capacity {
+                            model.pop_front();
+                        }
+                        model.push_back(v);
+                        buffer.push(v);
+                    }
+                    None => prop_assert_eq!(buffer.pop(), model.pop_front().unwrap_or_default()),
+                }
+                prop_assert_eq!(buffer.len(), model.len());
+                for (offset, expected) in model.iter().enumerate() {
+                    prop_assert_eq!(buffer.get(offset), *expected);
+                }
+            }
+        }
+
+        #[test]
+        fn prop_index_invariants(capacity in 1usize..16, ops in prop::collection::vec(op(), 0..200)) {
+            let mut buffer = RingBuffer::new(capacity);
+            for op in ops {
+                match op {
+                    Op::Push(v) => buffer.push(v),
+                    Op::Pop => {
+                        buffer.pop();
+                    }
+                    Op::SetReadIndex(i) => buffer.set_read_index(i),
+                    Op::SetWriteIndex(i) => buffer.set_write_index(i),
+                }
+                prop_assert_eq!(buffer.capacity(), capacity);
+                prop_assert!(buffer.len() <= capacity);
+                prop_assert!(buffer.get_read_index() < capacity);
+                prop_assert!(buffer.get_write_index() < capacity);
+                prop_assert_eq!((buffer.get_read_index() + buffer.len()) % capacity, buffer.get_write_index());
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_frac() {
+        let mut buffer = RingBuffer::new(4);
+        for i in 1..=4 {
+            buffer.push(i as f32);
+        }
+        assert_eq!(buffer.get_frac(0.0), 4.0);
+        assert_eq!(buffer.get_frac(3.0), 1.0);
+        assert_eq!(buffer.get_frac(0.25), 3.75);
+        assert_eq!(buffer.get_frac(-1.0), 4.0);
+        // Past the oldest value there is nothing older to blend with.
+        assert_eq!(buffer.get_frac(3.5), 1.0);
+        assert_eq!(buffer.get_frac(f32::INFINITY), 1.0);
+        assert_eq!(buffer.get_frac(f32::NAN), 4.0);
+        let mut output = [0.0; 9];
+        buffer.tap_frac_multi(&[f32::INFINITY; 9], &mut output);
+        assert_eq!(output, [1.0; 9]);
+    }
+
+    #[test]
+    fn test_get_frac_across_buffer_boundary() {
+        let mut buffer = RingBuffer::new(4);
+        for i in 1..=6 {
+            buffer.push(i as f32 * 10.0);
+        }
+        // Storage is [50, 60, 30, 40] with the write index at 2.
+        assert_eq!(buffer.get_write_index(), 2);
+        assert_eq!(buffer.get_frac(0.5), 55.0);
+        assert_eq!(buffer.get_frac(1.5), 45.0);
+        assert_eq!(buffer.get_frac(2.75), 32.5);
+    }
+
+    #[test]
+    fn test_tap_frac_multi() {
+        let mut buffer = RingBuffer::new(32);
+        for i in 0..40 {
+            buffer.push((i as f32 * 0.37).sin());
+        }
+        let delays: Vec<f32> = (0..19).map(|i| i as f32 * 1.3 - 0.5).collect();
+        let mut output = vec![0.0; 19];
+        buffer.tap_frac_multi(&delays, &mut output);
+        for (&delay, &value) in delays.iter().zip(&output) {
+            assert!((value - buffer.get_frac(delay)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_get_frac_cubic() {
+        let mut buffer = RingBuffer::new(8);
+        // Newest value last, so offset k holds (7 - k)^2.
+        for i in 0..8 {
+            buffer.push((i * i) as f32);
+        }
+        assert_eq!(buffer.get_frac_cubic(2.0), 25.0);
+        // Catmull-Rom is exact for quadratics away from the ends.
+        assert!((buffer.get_frac_cubic(2.5) - 20.25).abs() < 1e-4);
+        assert!((buffer.get_frac_cubic(4.25) - 7.5625).abs() < 1e-4);
+        assert_eq!(buffer.get_frac_cubic(5.5), 4.0);
+        assert_eq!(buffer.get_frac_cubic(f32::INFINITY), 4.0);
+    }
+
+    #[test]
+    fn test_get_frac_cubic_beats_linear_on_sine() {
+        let mut buffer = RingBuffer::new(64);
+        let signal = |n: f32| (n * 0.6).sin();
+        for n in 0..64 {
+            buffer.push(signal(n as f32));
+        }
+        let mut linear_error = 0.0f32;
+        let mut cubic_error = 0.0f32;
+        for k in 1..40 {
+            let offset = k as f32 + 0.5;
+            let expected = signal(63.0 - offset);
+            linear_error += (buffer.get_frac(offset) - expected).abs();
+            cubic_error += (buffer.get_frac_cubic(offset) - expected).abs();
+        }
+        assert!(cubic_error < linear_error / 4.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_get_frac_sinc() {
+        let table = SincTable::new(16);
+        assert_eq!(table.taps(), 16);
+        assert_eq!(SincTable::new(7).taps(), 8);
+
+        let mut buffer = RingBuffer::new(128);
+        let signal = |n: f32| (n * 1.3).sin();
+        for n in 0..128 {
+            buffer.push(signal(n as f32));
+        }
+        assert!((buffer.get_frac_sinc(20.0, &table) - signal(107.0)).abs() < 1e-5);
+
+        let mut sinc_error = 0.0f32;
+        let mut cubic_error = 0.0f32;
+        for k in 10..100 {
+            let offset = k as f32 + 0.37;
+            let expected = signal(127.0 - offset);
+            sinc_error += (buffer.get_frac_sinc(offset, &table) - expected).abs();
+            cubic_error += (buffer.get_frac_cubic(offset) - expected).abs();
+        }
+        assert!(sinc_error < cubic_error / 4.0);
+        let oldest = buffer.get_frac_sinc(f32::INFINITY, &table);
+        assert_eq!(oldest, buffer.get_frac_sinc((128 - 1 - table.taps() / 2) as f32, &table));
+    }
+
+    #[test]
+    fn test_get_frac_allpass() {
+        let mut buffer = RingBuffer::new(16);
+        let mut state = AllpassState::new();
+        let mut output = 0.0;
+        for _ in 0..200 {
+            buffer.push(1.0);
+            output = buffer.get_frac_allpass(3.3, &mut state);
+        }
+        assert!((output - 1.0).abs() < 1e-4);
+
+        // A slow sine comes out with unchanged amplitude and about the
+        // requested delay.
+        buffer.reset();
+        state.reset();
+        let signal = |n: f32| (n * 0.05).sin();
+        let mut max_error = 0.0f32;
+        for n in 0..400 {
+            buffer.push(signal(n as f32));
+            let value = buffer.get_frac_allpass(4.5, &mut state);
+            if n > 100 {
+                max_error = max_error.max((value - signal(n as f32 - 4.5)).abs());
+            }
+        }
+        assert!(max_error < 1e-3);
+    }
+
+    #[test]
+    fn test_get_frac_allpass_decays_after_short_offset() {
+        let mut buffer = RingBuffer::new(16);
+        let mut state = AllpassState::new();
+        for n in 0..300 {
+            buffer.push(if n < 20 { (n as f32 * 0.7).sin() } else { 0.0 });
+            // Jump from a long delay to one below the allpass minimum.
+            let offset = if n < 30 { 2.5 } else { 0.0 };
+            let output = buffer.get_frac_allpass(offset, &mut state);
+            if n > 250 {
+                assert!(output.abs() < 1e-6, "ringing at {}: {}", n, output);
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_from_and_read_into() {
+        let mut buffer = RingBuffer::new(4);
+        buffer.push(1);
+        buffer.push(2);
+        buffer.pop();
+        buffer.write_from(&[3, 4, 5]);
+        assert_eq!(buffer.len(), 4);
+        let mut output = [0; 3];
+        assert_eq!(buffer.read_into(&mut output), 3);
+        assert_eq!(output, [2, 3, 4]);
+
+        buffer.write_from(&[10, 11, 12, 13, 14, 15]);
+        let mut output = [0; 6];
+        assert_eq!(buffer.read_into(&mut output), 4);
+        assert_eq!(output, [12, 13, 14, 15, 0, 0]);
+        assert!(buffer.is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_bulk_matches_push_pop(
+            capacity in 1usize..16,
+            blocks in prop::collection::vec((prop::collection::vec(any::<i32>(), 0..24), 0usize..24), 0..20),
+        ) {
+            let mut bulk = RingBuffer::new(capacity);
+            let mut single = RingBuffer::new(capacity);
+            for (values, read_len) in blocks {
+                bulk.write_from(&values);
+                values.iter().for_each(|&v| single.push(v));
+                prop_assert_eq!(bulk.get_write_index(), single.get_write_index());
+                prop_assert_eq!(bulk.get_read_index(), single.get_read_index());
+
+                let mut output = vec![0; read_len];
+                let count = bulk.read_into(&mut output);
+                prop_assert_eq!(count, read_len.min(single.len()));
+                for value in &output[..count] {
+                    prop_assert_eq!(*value, single.pop());
+                }
+                prop_assert_eq!(bulk.len(), single.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_as_slices() {
+        let mut buffer = RingBuffer::new(4);
+        assert_eq!(buffer.as_slices(), (&[][..], &[][..]));
+        buffer.write_from(&[1, 2, 3]);
+        assert_eq!(buffer.as_slices(), (&[1, 2, 3][..], &[][..]));
+        buffer.write_from(&[4, 5]);
+        assert_eq!(buffer.as_slices(), (&[2, 3, 4][..], &[5][..]));
+
+        let (head, tail) = buffer.as_mut_slices();
+        head.iter_mut().chain(tail.iter_mut()).for_each(|v| *v *= 10);
+        assert_eq!(buffer.pop(), 20);
+        assert_eq!(buffer.get(2), 50);
+    }
 }
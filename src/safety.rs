@@ -0,0 +1,161 @@
+/// Replaces NaN/Inf samples with silence and remembers where the first one
+/// showed up, so a blown-up feedback path doesn't end up in the output file.
+pub struct SanityGuard {
+    position: usize,
+    bad_samples: usize,
+    first_bad_sample: Option<usize>,
+}
+
+impl SanityGuard {
+    pub fn new() -> Self {
+        SanityGuard {
+            position: 0,
+            bad_samples: 0,
+            first_bad_sample: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.position = 0;
+        self.bad_samples = 0;
+        self.first_bad_sample = None;
+    }
+
+    /// Returns the number of samples replaced in this block.
+    pub fn process(&mut self, block: &mut [f32]) -> usize {
+        let mut replaced = 0;
+        for (i, sample) in block.iter_mut().enumerate() {
+            if !sample.is_finite() {
+                *sample = 0.0;
+                replaced += 1;
+                self.first_bad_sample.get_or_insert(self.position + i);
+            }
+        }
+        self.position += block.len();
+        self.bad_samples += replaced;
+        replaced
+    }
+
+    pub fn bad_samples(&self) -> usize {
+        self.bad_samples
+    }
+
+    /// Stream position (in samples since the last reset) of the first bad sample.
+    pub fn first_bad_sample(&self) -> Option<usize> {
+        self.first_bad_sample
+    }
+}
+
+impl Default for SanityGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Output protection: samples above the threshold are bent with a tanh curve
+/// that approaches but never exceeds full scale.
+pub struct SoftClipper {
+    threshold: f32,
+    clipped_samples: usize,
+}
+
+impl SoftClipper {
+    pub fn new(threshold: f32) -> Self {
+        SoftClipper {
+            threshold: threshold.clamp(0.0, 1.0),
+            clipped_samples: 0,
+        }
+    }
+
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.threshold = threshold.clamp(0.0, 1.0);
+    }
+
+    pub fn reset(&mut self) {
+        self.clipped_samples = 0;
+    }
+
+    pub fn process_sample(&mut self, sample: f32) -> f32 {
+        let magnitude = sample.abs();
+        if magnitude <= self.threshold {
+            return sample;
+        }
+        self.clipped_samples += 1;
+        let headroom = 1.0 - self.threshold;
+        let shaped = if headroom > 0.0 {
+            self.threshold + headroom * ((magnitude - self.threshold) / headroom).tanh()
+        } else {
+            1.0
+        };
+        shaped.copysign(sample)
+    }
+
+    /// Returns the number of samples that were above the threshold.
+    pub fn process(&mut self, block: &mut [f32]) -> usize {
+        let before = self.clipped_samples;
+        for sample in block.iter_mut() {
+            *sample = self.process_sample(*sample);
+        }
+        self.clipped_samples - before
+    }
+
+    pub fn clipped_samples(&self) -> usize {
+        self.clipped_samples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SanityGuard, SoftClipper};
+
+    #[test]
+    fn test_replaces_non_finite_samples() {
+        let mut guard = SanityGuard::new();
+        let mut block = [0.5, f32::NAN, -0.25, f32::INFINITY, f32::NEG_INFINITY];
+        assert_eq!(guard.process(&mut block), 3);
+        assert_eq!(block, [0.5, 0.0, -0.25, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_tracks_first_position_across_blocks() {
+        let mut guard = SanityGuard::new();
+        let mut clean = [0.1; 8];
+        assert_eq!(guard.process(&mut clean), 0);
+        assert_eq!(guard.first_bad_sample(), None);
+
+        let mut block = [0.0, 0.0, f32::NAN, f32::NAN];
+        guard.process(&mut block);
+        assert_eq!(guard.first_bad_sample(), Some(10));
+        assert_eq!(guard.bad_samples(), 2);
+
+        guard.reset();
+        assert_eq!(guard.bad_samples(), 0);
+        assert_eq!(guard.first_bad_sample(), None);
+    }
+
+    #[test]
+    fn test_soft_clip_passes_signal_below_threshold() {
+        let mut clipper = SoftClipper::new(0.8);
+        let mut block = [0.0, 0.5, -0.8, 0.79];
+        assert_eq!(clipper.process(&mut block), 0);
+        assert_eq!(block, [0.0, 0.5, -0.8, 0.79]);
+    }
+
+    #[test]
+    fn test_soft_clip_limits_overs() {
+        let mut clipper = SoftClipper::new(0.8);
+        let mut block = [0.9, -1.5, 10.0, -100.0];
+        assert_eq!(clipper.process(&mut block), 4);
+        assert!(block.iter().all(|s| s.abs() <= 1.0 && s.abs() > 0.8));
+        assert!(block[0] > 0.0 && block[1] < 0.0);
+        assert!(block[2] > block[0]);
+        assert_eq!(clipper.clipped_samples(), 4);
+
+        let mut hard = SoftClipper::new(1.0);
+        assert_eq!(hard.process_sample(-3.0), -1.0);
+    }
+}
@@ -0,0 +1,136 @@
+use alloc::collections::VecDeque;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledEvent<E> {
+    pub time: u64,
+    pub event: E,
+}
+
+/// Queue of events stamped with absolute sample times. Each block, the caller
+/// drains the events that fall inside it along with their offset into the
+/// block and applies them between sub-block process calls.
+pub struct EventScheduler<E> {
+    queue: VecDeque<ScheduledEvent<E>>,
+    position: u64,
+}
+
+impl<E> EventScheduler<E> {
+    pub fn new() -> Self {
+        EventScheduler {
+            queue: VecDeque::new(),
+            position: 0,
+        }
+    }
+
+    /// Events at the same time are dispatched in the order they were scheduled.
+    pub fn schedule(&mut self, time: u64, event: E) {
+        let index = self.queue.partition_point(|scheduled| scheduled.time <= time);
+        self.queue.insert(index, ScheduledEvent { time, event });
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+
+    /// Sample time of the start of the next block.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn set_position(&mut self, position: u64) {
+        self.position = position;
+    }
+
+    /// Yields (offset, event) for every event before the end of the next
+    /// `block_size` samples, then advances the position. Late events are
+    /// dispatched at offset 0.
+    pub fn drain_block(&mut self, block_size: usize) -> DrainBlock<'_, E> {
+        let start = self.position;
+        self.position += block_size as u64;
+        DrainBlock {
+            queue: &mut self.queue,
+            start,
+            end: self.position,
+        }
+    }
+}
+
+impl<E> Default for EventScheduler<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct DrainBlock<'a, E> {
+    queue: &'a mut VecDeque<ScheduledEvent<E>>,
+    start: u64,
+    end: u64,
+}
+
+impl<E> Iterator for DrainBlock<'_, E> {
+    type Item = (usize, E);
+
+    fn next(&mut self) -> Option<(usize, E)> {
+        if self.queue.front()?.time >= self.end {
+            return None;
+        }
+        let scheduled = self.queue.pop_front()?;
+        Some((scheduled.time.saturating_sub(self.start) as usize, scheduled.event))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EventScheduler;
+
+    #[test]
+    fn test_events_land_in_their_block() {
+        let mut scheduler = EventScheduler::new();
+        scheduler.schedule(130, "c");
+        scheduler.schedule(5, "a");
+        scheduler.schedule(64, "b");
+
+        assert_eq!(scheduler.drain_block(64).collect::<Vec<_>>(), vec![(5, "a")]);
+        assert_eq!(scheduler.drain_block(64).collect::<Vec<_>>(), vec![(0, "b")]);
+        assert_eq!(scheduler.drain_block(64).collect::<Vec<_>>(), vec![(2, "c")]);
+        assert!(scheduler.is_empty());
+        assert_eq!(scheduler.position(), 192);
+    }
+
+    #[test]
+    fn test_same_time_keeps_order_and_late_events_fire_first() {
+        let mut scheduler = EventScheduler::new();
+        scheduler.set_position(100);
+        scheduler.schedule(110, 1);
+        scheduler.schedule(110, 2);
+        scheduler.schedule(50, 0);
+        assert_eq!(scheduler.len(), 3);
+        assert_eq!(scheduler.drain_block(32).collect::<Vec<_>>(), vec![(0, 0), (10, 1), (10, 2)]);
+    }
+
+    #[test]
+    fn test_sample_accurate_segments() {
+        // Apply gain changes exactly at their offsets by splitting the block.
+        let mut scheduler = EventScheduler::new();
+        scheduler.schedule(3, 0.5f32);
+        scheduler.schedule(6, 0.0f32);
+        let mut block = [1.0f32; 8];
+        let mut gain = 1.0;
+        let mut segment_start = 0;
+        for (offset, new_gain) in scheduler.drain_block(block.len()) {
+            block[segment_start..offset].iter_mut().for_each(|s| *s *= gain);
+            gain = new_gain;
+            segment_start = offset;
+        }
+        block[segment_start..].iter_mut().for_each(|s| *s *= gain);
+        assert_eq!(block, [1.0, 1.0, 1.0, 0.5, 0.5, 0.5, 0.0, 0.0]);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::units::ms_to_samples;
+
+const SNAP_THRESHOLD: f32 = 1e-6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmoothingMode {
+    /// Reaches the target exactly after the ramp time.
+    Linear,
+    /// Exponential approach, within 1% of the target after the ramp time.
+    OnePole,
+}
+
+/// Parameter value that glides towards its target instead of stepping,
+/// to avoid clicks on runtime parameter changes.
+#[derive(Debug, Clone)]
+pub struct SmoothedParam {
+    mode: SmoothingMode,
+    current: f32,
+    target: f32,
+    ramp_samples: usize,
+    step: f32,
+    remaining: usize,
+    coefficient: f32,
+}
+
+impl SmoothedParam {
+    pub fn new(value: f32, ramp_ms: f32, sample_rate: f32, mode: SmoothingMode) -> Self {
+        let mut param = SmoothedParam {
+            mode,
+            current: value,
+            target: value,
+            ramp_samples: 0,
+            step: 0.0,
+            remaining: 0,
+            coefficient: 0.0,
+        };
+        param.set_ramp(ramp_ms, sample_rate);
+        param
+    }
+
+    /// Changes the ramp time; an active glide continues at the new speed.
+    pub fn set_ramp(&mut self, ramp_ms: f32, sample_rate: f32) {
+        self.ramp_samples = ms_to_samples(ramp_ms.max(0.0), sample_rate).round() as usize;
+        // ln(100) time constants brings the one-pole within 1% of the target.
+        self.coefficient = if self.ramp_samples == 0 {
+            0.0
+        } else {
+            (-100.0f32.ln() / self.ramp_samples as f32).exp()
+        };
+        if self.remaining > 0 {
+            self.set_target(self.target);
+        }
+    }
+
+    pub fn set_target(&mut self, value: f32) {
+        self.target = value;
+        if self.ramp_samples == 0 {
+            self.set_immediate(value);
+            return;
+        }
+        self.remaining = self.ramp_samples;
+        self.step = (self.target - self.current) / self.ramp_samples as f32;
+    }
+
+    /// Jumps straight to `value`, cancelling any glide.
+    pub fn set_immediate(&mut self, value: f32) {
+        self.current = value;
+        self.target = value;
+        self.remaining = 0;
+    }
+
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    pub fn current(&self) -> f32 {
+        self.current
+    }
+
+    pub fn is_smoothing(&self) -> bool {
+        self.remaining > 0
+    }
+
+    pub fn next_value(&mut self) -> f32 {
+        if self.remaining == 0 {
+            return self.current;
+        }
+        match self.mode {
+            SmoothingMode::Linear => {
+                self.remaining -= 1;
+                self.current = if self.remaining == 0 {
+                    self.target
+                } else {
+                    self.current + self.step
+                };
+            }
+            SmoothingMode::OnePole => {
+                self.current = self.target + self.coefficient * (self.current - self.target);
+                if (self.current - self.target).abs() <= SNAP_THRESHOLD * self.target.abs().max(1.0) {
+                    self.current = self.target;
+                    self.remaining = 0;
+                }
+            }
+        }
+        self.current
+    }
+
+    pub fn fill(&mut self, output: &mut [f32]) {
+        for value in output.iter_mut() {
+            *value = self.next_value();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SmoothedParam, SmoothingMode};
+
+    #[test]
+    fn test_linear_ramp_reaches_target_on_time() {
+        let mut param = SmoothedParam::new(0.0, 1.0, 8000.0, SmoothingMode::Linear);
+        param.set_target(1.0);
+        let mut ramp = [0.0; 8];
+        param.fill(&mut ramp);
+        assert_eq!(ramp, [0.125, 0.25, 0.375, 0.5, 0.625, 0.75, 0.875, 1.0]);
+        assert!(!param.is_smoothing());
+        assert_eq!(param.next_value(), 1.0);
+    }
+
+    #[test]
+    fn test_one_pole_glides_monotonically() {
+        let mut param = SmoothedParam::new(1.0, 10.0, 1000.0, SmoothingMode::OnePole);
+        param.set_target(0.0);
+        let mut previous = 1.0;
+        for _ in 0..10 {
+            let value = param.next_value();
+            assert!(value < previous && value > 0.0);
+            previous = value;
+        }
+        assert!(previous < 0.011);
+        for _ in 0..1000 {
+            param.next_value();
+        }
+        assert_eq!(param.current(), 0.0);
+        assert!(!param.is_smoothing());
+    }
+
+    #[test]
+    fn test_zero_ramp_and_immediate() {
+        let mut param = SmoothedParam::new(0.5, 0.0, 44100.0, SmoothingMode::Linear);
+        param.set_target(2.0);
+        assert_eq!(param.next_value(), 2.0);
+
+        param.set_ramp(5.0, 44100.0);
+        param.set_target(3.0);
+        assert!(param.is_smoothing());
+        param.set_immediate(-1.0);
+        assert_eq!(param.next_value(), -1.0);
+        assert_eq!(param.target(), -1.0);
+    }
+}
@@ -0,0 +1,234 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Lock-free single-producer/single-consumer ring buffer for handing samples
+/// between two threads (e.g. a file reader and an audio callback). Unlike
+/// `RingBuffer`, a full buffer rejects new values instead of overwriting.
+pub struct SpscRingBuffer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+struct Shared<T> {
+    buffer: Vec<UnsafeCell<T>>,
+    capacity: usize,
+    // Total number of values ever read / written; slots are count & mask.
+    // The capacity is a power of two, so the slot sequence stays continuous
+    // when the counters wrap around usize::MAX.
+    mask: usize,
+    read_count: AtomicUsize,
+    write_count: AtomicUsize,
+}
+
+// Safety: a slot is only written by the producer while it is free and only
+// read by the consumer after the write was published through write_count,
+// so the two sides never touch the same slot at the same time.
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T: Copy + Default> SpscRingBuffer<T> {
+    /// `capacity` is rounded up to the next power of two.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1).next_power_of_two();
+        SpscRingBuffer {
+            shared: Arc::new(Shared {
+                buffer: (0..capacity).map(|_| UnsafeCell::new(T::default())).collect(),
+                capacity,
+                mask: capacity - 1,
+                read_count: AtomicUsize::new(0),
+                write_count: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    pub fn split(self) -> (Producer<T>, Consumer<T>) {
+        (
+            Producer {
+                shared: Arc::clone(&self.shared),
+            },
+            Consumer { shared: self.shared },
+        )
+    }
+}
+
+impl<T> Shared<T> {
+    fn len(&self) -> usize {
+        let written = self.write_count.load(Ordering::Acquire);
+        let read = self.read_count.load(Ordering::Acquire);
+        written.wrapping_sub(read)
+    }
+}
+
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Copy> Producer<T> {
+    /// Hands the value back when the buffer is full.
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        let shared = &*self.shared;
+        let written = shared.write_count.load(Ordering::Relaxed);
+        let read = shared.read_count.load(Ordering::Acquire);
+        if written.wrapping_sub(read) == shared.capacity {
+            return Err(value);
+        }
+        unsafe {
+            *shared.buffer[written & shared.mask].get() = value;
+        }
+        shared.write_count.store(written.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    /// Pushes as many values as fit and returns how many were written.
+    pub fn write_from(&mut self, values: &[T]) -> usize {
+        let shared = &*self.shared;
+        let written = shared.write_count.load(Ordering::Relaxed);
+        let read = shared.read_count.load(Ordering::Acquire);
+        let count = values.len().min(shared.capacity - written.wrapping_sub(read));
+        for (i, &value) in values[..count].iter().enumerate() {
+            unsafe {
+                *shared.buffer[written.wrapping_add(i) & shared.mask].get() = value;
+            }
+        }
+        shared.write_count.store(written.wrapping_add(count), Ordering::Release);
+        count
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == self.shared.capacity
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+}
+
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Copy> Consumer<T> {
+    pub fn pop(&mut self) -> Option<T> {
+        let shared = &*self.shared;
+        let read = shared.read_count.load(Ordering::Relaxed);
+        let written = shared.write_count.load(Ordering::Acquire);
+        if read == written {
+            return None;
+        }
+        let value = unsafe { *shared.buffer[read & shared.mask].get() };
+        shared.read_count.store(read.wrapping_add(1), Ordering::Release);
+        Some(value)
+    }
+
+    /// Pops up to `output.len()` values and returns how many were read.
+    pub fn read_into(&mut self, output: &mut [T]) -> usize {
+        let shared = &*self.shared;
+        let read = shared.read_count.load(Ordering::Relaxed);
+        let written = shared.write_count.load(Ordering::Acquire);
+        let count = output.len().min(written.wrapping_sub(read));
+        for (i, slot) in output[..count].iter_mut().enumerate() {
+            *slot = unsafe { *shared.buffer[read.wrapping_add(i) & shared.mask].get() };
+        }
+        shared.read_count.store(read.wrapping_add(count), Ordering::Release);
+        count
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.shared.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpscRingBuffer;
+    use core::sync::atomic::Ordering;
+
+    #[test]
+    fn test_push_pop_and_full() {
+        let (mut producer, mut consumer) = SpscRingBuffer::new(3).split();
+        assert_eq!(producer.capacity(), 4);
+        assert_eq!(consumer.pop(), None);
+        for i in 1..=4 {
+            assert_eq!(producer.push(i), Ok(()));
+        }
+        assert!(producer.is_full());
+        assert_eq!(producer.push(5), Err(5));
+        assert_eq!(consumer.pop(), Some(1));
+        assert_eq!(producer.push(5), Ok(()));
+        assert_eq!(consumer.len(), 4);
+        for i in 2..=5 {
+            assert_eq!(consumer.pop(), Some(i));
+        }
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn test_counters_wrap() {
+        let (mut producer, mut consumer) = SpscRingBuffer::new(3).split();
+        let start = usize::MAX - 5;
+        producer.shared.read_count.store(start, Ordering::Relaxed);
+        producer.shared.write_count.store(start, Ordering::Relaxed);
+        for i in 0..20 {
+            assert_eq!(producer.write_from(&[i, i + 100]), 2);
+            assert_eq!(consumer.pop(), Some(i));
+            assert_eq!(consumer.pop(), Some(i + 100));
+        }
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn test_bulk_transfer() {
+        let (mut producer, mut consumer) = SpscRingBuffer::new(4).split();
+        assert_eq!(producer.write_from(&[1.0, 2.0, 3.0, 4.0, 5.0]), 4);
+        let mut output = [0.0; 3];
+        assert_eq!(consumer.read_into(&mut output), 3);
+        assert_eq!(output, [1.0, 2.0, 3.0]);
+        assert_eq!(producer.write_from(&[5.0, 6.0, 7.0]), 3);
+        let mut output = [0.0; 8];
+        assert_eq!(consumer.read_into(&mut output), 4);
+        assert_eq!(&output[..4], &[4.0, 5.0, 6.0, 7.0]);
+    }
+
+    #[test]
+    fn test_threads_preserve_order() {
+        const COUNT: u32 = 100_000;
+        let (mut producer, mut consumer) = SpscRingBuffer::new(64).split();
+        let writer = std::thread::spawn(move || {
+            let mut next = 0;
+            while next < COUNT {
+                if producer.push(next).is_ok() {
+                    next += 1;
+                } else {
+                    std::thread::yield_now();
+                }
+            }
+        });
+
+        let mut expected = 0;
+        while expected < COUNT {
+            match consumer.pop() {
+                Some(value) => {
+                    assert_eq!(value, expected);
+                    expected += 1;
+                }
+                None => std::thread::yield_now(),
+            }
+        }
+        writer.join().unwrap();
+    }
+}
@@ -0,0 +1,105 @@
+use crate::units::{samples_to_seconds, seconds_to_samples};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteModifier {
+    Straight,
+    Dotted,
+    Triplet,
+}
+
+/// A note value as a fraction of a whole note, e.g. 1/8 dotted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoteDivision {
+    pub denominator: u32,
+    pub modifier: NoteModifier,
+}
+
+impl NoteDivision {
+    pub fn straight(denominator: u32) -> Self {
+        NoteDivision {
+            denominator,
+            modifier: NoteModifier::Straight,
+        }
+    }
+
+    pub fn dotted(denominator: u32) -> Self {
+        NoteDivision {
+            denominator,
+            modifier: NoteModifier::Dotted,
+        }
+    }
+
+    pub fn triplet(denominator: u32) -> Self {
+        NoteDivision {
+            denominator,
+            modifier: NoteModifier::Triplet,
+        }
+    }
+
+    /// Length in quarter-note beats.
+    pub fn beats(self) -> f32 {
+        let straight = 4.0 / self.denominator as f32;
+        match self.modifier {
+            NoteModifier::Straight => straight,
+            NoteModifier::Dotted => straight * 1.5,
+            NoteModifier::Triplet => straight * 2.0 / 3.0,
+        }
+    }
+
+    pub fn to_seconds(self, bpm: f32) -> f32 {
+        beats_to_seconds(self.beats(), bpm)
+    }
+
+    pub fn to_samples(self, bpm: f32, sample_rate: f32) -> f32 {
+        seconds_to_samples(self.to_seconds(bpm), sample_rate)
+    }
+
+    /// Repetition rate, e.g. for syncing an LFO to the note value.
+    pub fn to_hz(self, bpm: f32) -> f32 {
+        1.0 / self.to_seconds(bpm)
+    }
+
+    /// Tempo at which this note value lasts `seconds`.
+    pub fn bpm_for_seconds(self, seconds: f32) -> f32 {
+        60.0 * self.beats() / seconds
+    }
+}
+
+pub fn beats_to_seconds(beats: f32, bpm: f32) -> f32 {
+    beats * 60.0 / bpm
+}
+
+pub fn seconds_to_beats(seconds: f32, bpm: f32) -> f32 {
+    seconds * bpm / 60.0
+}
+
+pub fn samples_to_beats(samples: f32, bpm: f32, sample_rate: f32) -> f32 {
+    seconds_to_beats(samples_to_seconds(samples, sample_rate), bpm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-3, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn test_note_lengths() {
+        assert_close(NoteDivision::straight(4).to_seconds(120.0), 0.5);
+        assert_close(NoteDivision::straight(1).to_seconds(120.0), 2.0);
+        assert_close(NoteDivision::dotted(8).to_seconds(120.0), 0.375);
+        assert_close(NoteDivision::triplet(8).to_seconds(120.0), 1.0 / 6.0);
+        assert_close(NoteDivision::straight(16).to_samples(120.0, 48000.0), 6000.0);
+        assert_close(NoteDivision::straight(8).to_hz(120.0), 4.0);
+    }
+
+    #[test]
+    fn test_round_trips() {
+        assert_close(seconds_to_beats(beats_to_seconds(3.5, 97.0), 97.0), 3.5);
+        assert_close(samples_to_beats(22050.0, 120.0, 44100.0), 1.0);
+        let division = NoteDivision::dotted(4);
+        assert_close(division.bpm_for_seconds(division.to_seconds(133.0)), 133.0);
+    }
+}
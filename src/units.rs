@@ -0,0 +1,101 @@
+pub const A4_HZ: f32 = 440.0;
+pub const A4_NOTE: f32 = 69.0;
+
+#[cfg(feature = "std")]
+pub fn db_to_gain(db: f32) -> f32 {
+    10.0f32.powf(db / 20.0)
+}
+
+/// Silence maps to -inf dB.
+#[cfg(feature = "std")]
+pub fn gain_to_db(gain: f32) -> f32 {
+    20.0 * gain.abs().log10()
+}
+
+pub fn seconds_to_samples(seconds: f32, sample_rate: f32) -> f32 {
+    seconds * sample_rate
+}
+
+pub fn samples_to_seconds(samples: f32, sample_rate: f32) -> f32 {
+    samples / sample_rate
+}
+
+pub fn ms_to_samples(ms: f32, sample_rate: f32) -> f32 {
+    seconds_to_samples(ms / 1000.0, sample_rate)
+}
+
+pub fn samples_to_ms(samples: f32, sample_rate: f32) -> f32 {
+    samples_to_seconds(samples, sample_rate) * 1000.0
+}
+
+/// MIDI note numbers, fractional notes allowed (69 = A4 = 440 Hz).
+#[cfg(feature = "std")]
+pub fn note_to_hz(note: f32) -> f32 {
+    A4_HZ * semitones_to_ratio(note - A4_NOTE)
+}
+
+#[cfg(feature = "std")]
+pub fn hz_to_note(hz: f32) -> f32 {
+    A4_NOTE + ratio_to_semitones(hz / A4_HZ)
+}
+
+#[cfg(feature = "std")]
+pub fn semitones_to_ratio(semitones: f32) -> f32 {
+    (semitones / 12.0).exp2()
+}
+
+#[cfg(feature = "std")]
+pub fn ratio_to_semitones(ratio: f32) -> f32 {
+    12.0 * ratio.log2()
+}
+
+#[cfg(feature = "std")]
+pub fn cents_to_ratio(cents: f32) -> f32 {
+    (cents / 1200.0).exp2()
+}
+
+#[cfg(feature = "std")]
+pub fn ratio_to_cents(ratio: f32) -> f32 {
+    1200.0 * ratio.log2()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-3, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_decibels() {
+        assert_close(db_to_gain(0.0), 1.0);
+        assert_close(db_to_gain(-6.0206), 0.5);
+        assert_close(gain_to_db(0.1), -20.0);
+        assert_close(gain_to_db(-0.1), -20.0);
+        assert_eq!(gain_to_db(0.0), f32::NEG_INFINITY);
+        assert_close(gain_to_db(db_to_gain(-37.5)), -37.5);
+    }
+
+    #[test]
+    fn test_time() {
+        assert_close(ms_to_samples(10.0, 44100.0), 441.0);
+        assert_close(samples_to_ms(441.0, 44100.0), 10.0);
+        assert_close(seconds_to_samples(0.5, 48000.0), 24000.0);
+        assert_close(samples_to_seconds(24000.0, 48000.0), 0.5);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_pitch() {
+        assert_close(note_to_hz(69.0), 440.0);
+        assert_close(note_to_hz(60.0), 261.6256);
+        assert_close(hz_to_note(880.0), 81.0);
+        assert_close(hz_to_note(note_to_hz(61.3)), 61.3);
+        assert_close(semitones_to_ratio(12.0), 2.0);
+        assert_close(ratio_to_semitones(0.5), -12.0);
+        assert_close(cents_to_ratio(1200.0), 2.0);
+        assert_close(ratio_to_cents(cents_to_ratio(-35.0)), -35.0);
+    }
+}